@@ -0,0 +1,195 @@
+use crate::events::{GameEvent, Subscriber};
+use crate::modifiers::Modifiers;
+use crate::utils::unix_timestamp;
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// What has to happen to unlock an achievement
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Goal {
+    Lengths { count: u64 },  // Lengths swum by the team, across every season
+    Upgrades { count: u64 }, // Swimmer upgrades bought
+    Hires { count: u64 },    // Swimmers hired
+    TeamSize { count: u64 }, // Swimmers in the team at once
+    Medals { count: u64 },   // Medals won at meets
+    Relays { count: u64 },   // Relays swum to the end
+}
+
+/// The permanent bonus an unlocked achievement gives the whole team
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AchievementBonus {
+    Speed { bonus: f64 },              // Faster swimmers
+    UpgradeDiscount { discount: f64 }, // Cheaper swimmer upgrades
+    HireSpeed { bonus: f64 },          // Extra starting speed for new hires
+}
+
+impl AchievementBonus {
+    /// Gets a short description of the bonus
+    pub fn description(self) -> String {
+        match self {
+            AchievementBonus::Speed { bonus } => format!("+{:.0}% speed", bonus * 100_f64),
+            AchievementBonus::UpgradeDiscount { discount } => {
+                format!("-{:.0}% on upgrades", discount * 100_f64)
+            }
+            AchievementBonus::HireSpeed { bonus } => {
+                format!("+{:.2} starting speed for new hires", bonus)
+            }
+        }
+    }
+}
+
+/// A milestone the team can reach once
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Achievement {
+    pub id: String,          // Stable identifier, what the save file records
+    pub name: String,        // Name shown in the UI
+    pub description: String, // What it takes, shown in the UI
+    pub goal: Goal,
+    pub bonus: AchievementBonus,
+}
+
+/// Every achievement that can be unlocked, loaded from `src/data/achievements.json`
+#[derive(Default, Deserialize)]
+pub struct AchievementData {
+    pub achievements: Vec<Achievement>,
+}
+
+/// Running totals of everything achievements are measured against, kept across seasons
+#[derive(Default, Serialize, Deserialize)]
+pub struct AchievementStats {
+    pub lengths: u64,   // Lengths swum
+    pub upgrades: u64,  // Swimmer upgrades bought
+    pub hires: u64,     // Swimmers hired
+    pub team_size: u64, // Most swimmers in the team at once
+    pub medals: u64,    // Medals won at meets
+    pub relays: u64,    // Relays completed
+}
+
+/// The achievements unlocked so far and the totals that unlock the rest
+#[derive(Default, Serialize, Deserialize)]
+pub struct Achievements {
+    pub stats: AchievementStats,
+    unlocked: BTreeMap<String, u64>, // Unix timestamp each achievement was unlocked at, by id
+}
+
+impl Achievements {
+    /// Gets how far the team is towards an achievement's goal
+    ///
+    /// # Arguments
+    /// * `achievement` - The achievement
+    ///
+    /// # Returns
+    /// The current total and the total needed
+    pub fn progress(&self, achievement: &Achievement) -> (u64, u64) {
+        let stats: &AchievementStats = &self.stats;
+        match achievement.goal {
+            Goal::Lengths { count } => (stats.lengths, count),
+            Goal::Upgrades { count } => (stats.upgrades, count),
+            Goal::Hires { count } => (stats.hires, count),
+            Goal::TeamSize { count } => (stats.team_size, count),
+            Goal::Medals { count } => (stats.medals, count),
+            Goal::Relays { count } => (stats.relays, count),
+        }
+    }
+
+    /// Gets when an achievement was unlocked
+    ///
+    /// # Arguments
+    /// * `achievement` - The achievement
+    ///
+    /// # Returns
+    /// The Unix timestamp of the unlock, or `None` if it is still locked
+    pub fn unlocked_at(&self, achievement: &Achievement) -> Option<u64> {
+        self.unlocked.get(&achievement.id).copied()
+    }
+
+    /// Counts the achievements unlocked so far
+    pub fn unlocked_count(&self) -> usize {
+        self.unlocked.len()
+    }
+
+    /// Unlocks every achievement whose goal has been reached since the last check
+    ///
+    /// # Arguments
+    /// * `data` - The achievement catalogue
+    ///
+    /// # Returns
+    /// The achievements unlocked by this check
+    pub fn unlock<'a>(&mut self, data: &'a AchievementData) -> Vec<&'a Achievement> {
+        let reached: Vec<&Achievement> = data
+            .achievements
+            .iter()
+            .filter(|achievement: &&Achievement| {
+                let (current, target): (u64, u64) = self.progress(achievement);
+                current >= target && !self.unlocked.contains_key(&achievement.id)
+            })
+            .collect();
+
+        let now: u64 = unix_timestamp();
+        for achievement in &reached {
+            self.unlocked.insert(achievement.id.clone(), now);
+        }
+        reached
+    }
+
+    /// Applies the bonus of every unlocked achievement to the modifiers
+    ///
+    /// # Arguments
+    /// * `data` - The achievement catalogue
+    /// * `modifiers` - The modifiers to update
+    pub fn apply(&self, data: &AchievementData, modifiers: &mut Modifiers) {
+        for achievement in &data.achievements {
+            if !self.unlocked.contains_key(&achievement.id) {
+                continue;
+            }
+            match achievement.bonus {
+                AchievementBonus::Speed { bonus } => {
+                    modifiers.speed_multiplier *= 1_f64 + bonus;
+                }
+                AchievementBonus::UpgradeDiscount { discount } => {
+                    modifiers.upgrade_cost_multiplier *= 1_f64 - discount;
+                }
+                AchievementBonus::HireSpeed { bonus } => {
+                    modifiers.hire_speed_bonus += bonus;
+                }
+            }
+        }
+    }
+}
+
+impl Subscriber for Achievements {
+    /// Counts every event towards the totals achievements are measured against
+    ///
+    /// # Arguments
+    /// * `event` - The event that happened
+    fn on_event(&mut self, event: &GameEvent) {
+        let stats: &mut AchievementStats = &mut self.stats;
+        match event {
+            GameEvent::LengthsCompleted { lengths, .. } => stats.lengths += lengths,
+            GameEvent::Upgraded { .. } => stats.upgrades += 1_u64,
+            GameEvent::SwimmerHired { team_size, .. } => {
+                stats.hires += 1_u64;
+                stats.team_size = stats.team_size.max(*team_size as u64);
+            }
+            GameEvent::RaceFinished { medals, .. } => stats.medals += medals,
+            GameEvent::RelayFinished { .. } => stats.relays += 1_u64,
+            _ => {}
+        }
+    }
+}
+
+/// The achievement catalogue, built into the game so it runs from any directory
+const ACHIEVEMENT_DATA: &str = include_str!("data/achievements.json");
+
+/// Loads the achievement catalogue from the JSON built into the game
+///
+/// # Returns
+/// A Result containing the AchievementData or an error
+pub fn load_achievement_data() -> Result<AchievementData> {
+    let achievement_data: AchievementData =
+        serde_json::from_str::<AchievementData>(ACHIEVEMENT_DATA).into_diagnostic()?;
+    Ok(achievement_data)
+}
@@ -0,0 +1,55 @@
+use serde::Deserialize;
+
+/// Something the player asks the game to do
+///
+/// Actions carry no knowledge of keys or terminals: the game decides what
+/// each one means on the screen it is showing, so the same action can move
+/// the highlight on one screen and buy an upgrade on another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Move the highlight up one entry
+    SelectPrev,
+    /// Move the highlight down one entry
+    SelectNext,
+    /// Move the highlight up one page of swimmers
+    PageUp,
+    /// Move the highlight down one page of swimmers
+    PageDown,
+    /// Move the highlight to the first swimmer
+    SelectFirst,
+    /// Move the highlight to the last swimmer
+    SelectLast,
+    /// Offer the previous option of the highlighted entry, like another stroke
+    CyclePrev,
+    /// Offer the next option of the highlighted entry
+    CycleNext,
+    /// Open the swimmer's details, buy the highlighted entry or start what was picked
+    Confirm,
+    /// Open the swimmer's upgrades, buy the highlighted entry or pick the highlighted swimmer
+    Toggle,
+    /// Leave the current screen
+    Back,
+    /// Quit from the pool, leave the current screen anywhere else
+    Quit,
+    /// Hire a new swimmer
+    Hire,
+    /// Bench the selected swimmer or send them back into the pool
+    Rest,
+    /// Convert lengths into points
+    Convert,
+    /// Open or close the points shop
+    Shop,
+    /// Open or close the season screen
+    Season,
+    /// Open or close the entry screen of the announced meet
+    Meet,
+    /// Open or close the league table
+    League,
+    /// Form a relay with the selected swimmer, or disband theirs
+    Relay,
+    /// Open or close the facilities screen
+    Facilities,
+    /// Open or close the achievements screen
+    Achievements,
+}
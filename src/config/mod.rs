@@ -0,0 +1,64 @@
+//! Config module for user settings loaded from `config.json`
+
+use crate::action::Action;
+use miette::{IntoDiagnostic, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Location of the optional config file, relative to the working directory
+pub const CONFIG_PATH: &str = "config.json";
+
+/// User settings, every field falls back to its default when missing
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub autosave_interval_secs: u64, // Seconds between autosaves, 0 disables autosave
+    pub backup_count: usize,         // Number of rotating save backups to keep
+    pub exchange: ExchangeConfig,    // Rate at which lengths are converted to points
+    pub keys: BTreeMap<String, Option<Action>>, // Key bindings on top of the defaults, `null` unbinds a key
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            autosave_interval_secs: 60_u64,
+            backup_count: 3_usize,
+            exchange: ExchangeConfig::default(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+/// Settings for converting lengths into points
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct ExchangeConfig {
+    pub lengths_per_point: f64, // Price of the very first point, in lengths
+    pub diminishing_rate: f64,  // How much pricier each point gets per point already converted
+}
+
+impl Default for ExchangeConfig {
+    fn default() -> Self {
+        Self {
+            lengths_per_point: 10_f64,
+            diminishing_rate: 0.05_f64,
+        }
+    }
+}
+
+/// Loads the config file, or the default config if there is none
+///
+/// # Returns
+/// A Result containing the Config or an error
+pub fn load_config() -> Result<Config> {
+    let path: &Path = Path::new(CONFIG_PATH);
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let data: String = fs::read_to_string(path).into_diagnostic()?;
+    let config: Config = serde_json::from_str::<Config>(&data).into_diagnostic()?;
+    Ok(config)
+}
@@ -0,0 +1,177 @@
+use crate::league::SeasonOutcome;
+use crate::pool::PoolLength;
+use crate::stroke::Stroke;
+use crate::swimmer::Stat;
+
+/// What a failed purchase would have been paid with
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Currency {
+    Lengths,         // Team lengths in the wallet
+    Points,          // Points in the wallet
+    PrizeMoney,      // Prize money won at meets
+    Trophies,        // Trophies kept across seasons
+    LifetimeLengths, // Lengths swum this season, which earn trophies
+}
+
+impl Currency {
+    /// Gets the display name of the currency
+    pub fn name(self) -> &'static str {
+        match self {
+            Currency::Lengths => "lengths",
+            Currency::Points => "points",
+            Currency::PrizeMoney => "prize money",
+            Currency::Trophies => "trophies",
+            Currency::LifetimeLengths => "lifetime lengths",
+        }
+    }
+}
+
+/// Something that happened in the game
+///
+/// The game emits these as it changes and hands every one to each subscriber
+/// once the tick or key press is over, so new systems can react to the game
+/// without the code that made the change knowing about them.
+#[derive(Clone)]
+pub enum GameEvent {
+    LengthsCompleted {
+        lengths: u64, // Lengths the team swam, counted in 25 m lengths
+    },
+    LevelledUp {
+        swimmer: String, // Swimmer who levelled up
+        level: u32,      // Level reached
+    },
+    Exhausted {
+        swimmer: String, // Swimmer who ran out of energy
+    },
+    PerkLearned {
+        swimmer: String, // Swimmer who picked the perk
+        perk: String,    // Name of the perk
+    },
+    Upgraded {
+        swimmer: String, // Swimmer who was upgraded
+        stat: Stat,      // Stat that was upgraded
+        value: String,   // New value of the stat, formatted for display
+    },
+    Retrained {
+        swimmer: String, // Swimmer who was retrained
+        stroke: Stroke,  // Stroke they swim now
+    },
+    SwimmerHired {
+        swimmer: String,  // Name of the new swimmer
+        speed: f64,       // Their starting speed
+        team_size: usize, // Swimmers in the team now
+    },
+    RestToggled {
+        swimmer: String, // Swimmer who got out of or back into the pool
+        resting: bool,   // Whether they are on the bench now
+    },
+    LengthsConverted {
+        points: u64, // Points bought
+    },
+    Purchased {
+        item: &'static str, // Name of the shop item, facility or prestige bonus
+        level: u32,         // Level it is at now
+    },
+    PurchaseFailed {
+        item: String,       // What the player tried to buy
+        currency: Currency, // What it costs
+        needed: u64,        // How much more of the currency it takes
+    },
+    PoolLengthChanged {
+        length: PoolLength, // Length of the pool the team trains in now
+    },
+    SeasonStarted {
+        season: u32,   // Number of the season that just began
+        trophies: u64, // Trophies the last one earned
+    },
+    LanesFull {
+        lanes: usize, // Lanes the pool has
+    },
+    MeetAnnounced {
+        meet: String, // Name of the meet
+    },
+    MeetNotOpen {
+        seconds: u64, // Time until the next meet is announced
+    },
+    MeetFull {
+        entrants: usize, // Swimmers a meet takes from the team
+    },
+    NoEntrants,
+    RaceFinished {
+        meet: String,      // Name of the meet
+        best_place: usize, // Place of the team's best finisher
+        medals: u64,       // Medals the team won
+        prize_money: u64,  // Prize money the team won
+    },
+    LeagueWeekRaced {
+        week: u32,        // Week of the league season that was raced
+        meet: String,     // Name of the fixture
+        place: usize,     // Place of the team's club
+        prize_money: u64, // Prize money the team won
+    },
+    LeagueSeasonEnded {
+        outcome: SeasonOutcome, // Whether the club went up, down or stayed
+        division: usize,        // Division the club plays in now, 0 is the top one
+    },
+    RelayFormed {
+        relay: String,    // Name of the relay
+        leg_lengths: u64, // Lengths each member swims in turn
+    },
+    RelayDisbanded {
+        relay: String, // Name of the relay
+    },
+    RelayFinished {
+        relay: String,      // Name of the relay
+        ticks: u64,         // Time the relay took
+        bonus_lengths: u64, // Lengths it earned the team
+    },
+    RelayMemberBusy {
+        swimmer: String, // Swimmer who is in a relay
+        relay: String,   // Name of their relay
+        resting: bool,   // Whether the player tried to bench them, rather than pick them
+    },
+    RelayShort {
+        missing: usize, // Swimmers still needed for a relay
+        picked: bool,   // Whether swimmers are missing from the picks, rather than the team
+    },
+    RelayFull {
+        legs: usize, // Swimmers in a relay
+    },
+    AchievementUnlocked {
+        name: String,  // Name of the achievement
+        bonus: String, // Description of its bonus
+    },
+}
+
+/// Something that reacts to the game's events
+pub trait Subscriber {
+    /// Reacts to one event
+    ///
+    /// # Arguments
+    /// * `event` - The event that happened
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+/// Events emitted since they were last handed to the subscribers, oldest first
+#[derive(Default)]
+pub struct EventBus {
+    queue: Vec<GameEvent>,
+}
+
+impl EventBus {
+    /// Queues an event for the subscribers
+    ///
+    /// # Arguments
+    /// * `event` - The event that happened
+    pub fn emit(&mut self, event: GameEvent) {
+        self.queue.push(event);
+    }
+
+    /// Takes every queued event, leaving the queue empty
+    ///
+    /// # Returns
+    /// The events, oldest first
+    pub fn take(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.queue)
+    }
+}
@@ -1,43 +1,1503 @@
-use crate::swimmer::Swimmer;
-use crate::ui;
-use crate::utils::{generate_random_name, load_name_data, NameData};
-use miette::{IntoDiagnostic, Result};
-use std::thread;
-use std::time::{Duration, Instant};
-
-use crossterm::{
-    event::{self, read, Event, KeyCode},
-    terminal::{disable_raw_mode, enable_raw_mode},
-};
+use crate::achievements::{load_achievement_data, Achievement, AchievementData, Achievements};
+use crate::action::Action;
+use crate::config::{load_config, Config};
+use crate::events::{Currency, EventBus, GameEvent, Subscriber};
+use crate::keymap::Keymap;
+use crate::league::{League, SeasonOutcome, MAX_OFFLINE_WEEKS};
+use crate::modifiers::Modifiers;
+use crate::notifications::Notifications;
+use crate::perks::{load_perk_data, Perk, PerkData};
+use crate::pool::{Facility, Pool};
+use crate::prestige::{Prestige, PrestigeItem};
+use crate::race::{Medals, Meet, Race, MAX_ENTRANTS, MEET_INTERVAL_TICKS, TEAM_CLUB};
+use crate::relay::{Handover, Relay, LEG_LENGTHS, RELAY_BONUS_LENGTHS, RELAY_SIZE};
+use crate::rng::GameRng;
+use crate::save::{self, Backup, SAVE_DIR, SAVE_PATH};
+use crate::shop::{Shop, ShopItem};
+use crate::stroke::Stroke;
+use crate::swimmer::{Stat, Swimmer};
+use crate::utils::{generate_random_name, load_name_data, unix_timestamp, NameData};
+use crate::wallet::Wallet;
+use miette::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
 
+/// Fixed time step between simulation ticks (~30 FPS)
+pub const FRAME_DURATION_MS: u64 = 33_u64;
+
+/// Ticks between samples of every swimmer's speed for their sparkline (~5 seconds)
+const SPEED_SAMPLE_TICKS: u64 = 150_u64;
+
+/// Relay starts remembered while crediting offline progress, to spot when the relays start repeating
+const RELAY_STARTS_KEPT: usize = 8_usize;
+
+/// Which screen the player is looking at
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Screen {
+    #[default]
+    Pool, // The swimmer lanes
+    Upgrade {
+        selected_index: usize, // Index into `Stat::ALL`, one past the end is the stroke
+        retrain_to: Stroke,    // Stroke offered when retraining the selected swimmer
+    },
+    Details {
+        selected_index: usize, // Index into the selected swimmer's perk offer
+    },
+    Shop {
+        selected_index: usize, // Index into `ShopItem::ALL` of the highlighted item
+    },
+    Season {
+        selected_index: usize, // Index into `PrestigeItem::ALL`, one past the end is "New season"
+    },
+    Meet {
+        selected_index: usize, // Index into the swimmers of the highlighted swimmer
+    },
+    Race,   // The heat being swum, then its results
+    League, // The league table and upcoming fixtures
+    Relay {
+        selected_index: usize, // Index into the swimmers of the highlighted swimmer
+    },
+    Facilities {
+        selected_index: usize, // Index into `Facility::ALL`, one past the end is the pool length
+    },
+    Achievements {
+        selected_index: usize, // Index into the achievement catalogue of the highlighted entry
+    },
+}
+
+/// Summary of the progress credited for the time the game was closed
+pub struct OfflineProgress {
+    pub elapsed: Duration, // Time since the game was last saved
+    pub lengths_by_swimmer: Vec<(String, u64)>, // Lengths each swimmer completed while away
+    pub league_weeks: u64, // Weekly league meets raced while away
+    pub relays: u64,       // Relays completed while away
+    pub relay_bonus_lengths: u64, // Bonus lengths the relays earned while away
+}
+
+/// Where a relay stood at the start of a relay swum offline, to spot when relays start repeating
+struct RelayStart {
+    members: Vec<(f64, u64)>, // Each member's energy and how many lengths into their stroke's cycle they are
+    ticks_left: u64,          // Offline ticks still to swim
+    completed: u64,           // Relays completed offline by then
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Game {
     swimmers: Vec<Swimmer>,
     selected_index: usize,
+    #[serde(skip)]
     name_data: NameData,
+    #[serde(skip)]
+    perk_data: PerkData,
+    #[serde(skip)]
+    achievement_data: AchievementData,
     new_swimmer_cost: usize,
+    #[serde(default)]
+    wallet: Wallet, // Team treasury every earned length flows into
+    #[serde(default)]
+    shop: Shop, // Permanent upgrades bought with points
+    #[serde(default)]
+    prestige: Prestige, // Trophies and bonuses kept across seasons
+    #[serde(default)]
+    meet: Option<Meet>, // Meet open for entries, if one has been announced
+    #[serde(default)]
+    ticks_since_meet: u64, // Ticks since the last meet was raced, the next is announced after an interval
+    #[serde(default)]
+    medals: Medals, // Medals the team has won at meets, kept across seasons
+    #[serde(default)]
+    league: League, // The league of AI clubs the team plays a meet against every week
+    #[serde(default)]
+    relays: Vec<Relay>, // Relay teams, each swimming their legs in a shared lane
+    #[serde(default)]
+    pool: Pool, // The team's home pool, its lanes and facilities, kept across seasons
+    #[serde(default)]
+    achievements: Achievements, // Milestones unlocked so far and the totals behind them, kept across seasons
+    #[serde(default)]
+    rng: GameRng, // Every random draw comes from here, older saves get a random seed
+    #[serde(default)]
+    saved_at: u64, // Unix timestamp of the last save, used for offline progress
+    #[serde(skip)]
+    offline_progress: Option<OfflineProgress>,
+    #[serde(skip)]
+    config: Config,
+    #[serde(skip)]
+    keymap: Keymap, // Which keys ask for which action, the defaults with the config's bindings on top
+    #[serde(skip)]
+    notifications: Notifications,
+    #[serde(skip)]
+    events: EventBus, // Events emitted since they were last handed to the subscribers
+    #[serde(skip)]
+    screen: Screen,
+    #[serde(skip)]
+    ticks_since_sample: u64, // Ticks since every swimmer's speed was last sampled
+    #[serde(skip)]
+    entrants: Vec<usize>, // Swimmers picked to enter the open meet
+    #[serde(skip)]
+    race: Option<Race>, // The heat being swum or whose results are on screen
+    #[serde(skip)]
+    relay_picks: Vec<usize>, // Swimmers picked for the relay being formed, in leg order
+    #[serde(skip)]
+    page_size: usize, // Swimmers that fit on screen, for paging
+    #[serde(skip)]
+    unsimulated: Duration, // Time passed to `tick` that is not yet a whole tick
 }
 
 impl Game {
-    /// Creates a new game with initial state
+    /// Resumes the game from the save file, or starts a new one if there is none
+    ///
+    /// # Arguments
+    /// * `seed` - Seed for a new game, a random one if `None`. A resumed game
+    ///   keeps the seed it was started with, so asking for another is an error
+    /// * `choose_backup` - Asks the player which backup to restore if the save file
+    ///   is corrupt, given every backup newest first, and returns its index or `None`.
+    ///   It is asked again without any backup that turns out to be corrupt as well
     ///
     /// # Returns
-    /// A Result containing the new Game or an error
-    pub fn new() -> Result<Self> {
-        // Load name data
+    /// A Result containing the Game or an error
+    pub fn load<F>(seed: Option<u64>, choose_backup: F) -> Result<Self>
+    where
+        F: FnMut(&[Backup]) -> Result<Option<usize>>,
+    {
+        let config: Config = load_config()?;
+
+        // Resume the previous session if there is a save file, falling back to a backup
+        let dir: &Path = Path::new(SAVE_DIR);
+        let saved_game: Option<Game> = match save::load_save::<Game>(&dir.join(SAVE_PATH)) {
+            Ok(saved_game) => saved_game,
+            Err(report) => Some(Self::restore_backup(
+                dir,
+                report,
+                config.backup_count,
+                choose_backup,
+            )?),
+        };
+        let game: Game = Self::resume(saved_game, seed, config)?;
+
+        // The save the session started from becomes the newest backup, autosaves only replace the save
+        save::rotate_backups(dir, game.config.backup_count)?;
+        Ok(game)
+    }
+
+    /// Carries on a saved game, or starts a new one if there is none
+    ///
+    /// # Arguments
+    /// * `saved_game` - The game read from the save file, if there was one
+    /// * `seed` - Seed for a new game, a random one if `None`. A saved game
+    ///   keeps the seed it was started with, so asking for another is an error
+    /// * `config` - The user settings
+    ///
+    /// # Returns
+    /// A Result containing the Game or an error
+    fn resume(saved_game: Option<Game>, seed: Option<u64>, config: Config) -> Result<Self> {
+        // Load name data and key bindings
         let name_data: NameData = load_name_data()?;
+        let perk_data: PerkData = load_perk_data()?;
+        let achievement_data: AchievementData = load_achievement_data()?;
+        let keymap: Keymap = Keymap::with_overrides(&config.keys)?;
+
+        if let Some(mut game) = saved_game {
+            if let Some(seed) = seed.filter(|seed: &u64| *seed != game.rng.seed()) {
+                return Err(miette::miette!(
+                    help = format!(
+                        "Move {} aside to start a new game with seed {}",
+                        SAVE_PATH, seed
+                    ),
+                    "The saved game was started with seed {}",
+                    game.rng.seed()
+                ));
+            }
+            game.name_data = name_data;
+            game.perk_data = perk_data;
+            game.achievement_data = achievement_data;
+            game.config = config;
+            game.notifications = Notifications::new(keymap.clone());
+            game.keymap = keymap;
+            if game.swimmers.is_empty() {
+                let first_name: String = generate_random_name(&game.name_data, &mut game.rng);
+                game.swimmers
+                    .push(Swimmer::new(&first_name, 0.7_f64, &mut game.rng));
+            }
+            if game.league.is_empty() {
+                game.league = League::new(&game.name_data, &mut game.rng);
+            }
+            game.league.rate_clubs(&mut game.rng);
+            let swimmer_count: usize = game.swimmers.len();
+            game.relays.retain(|relay: &Relay| {
+                relay
+                    .members
+                    .iter()
+                    .all(|member: &usize| *member < swimmer_count)
+            });
+            game.selected_index = game.selected_index.min(game.swimmers.len() - 1_usize);
+
+            // Saves from before the team wallet kept the lengths to spend on each swimmer
+            let legacy_lengths: u64 = game
+                .swimmers
+                .iter_mut()
+                .map(Swimmer::take_legacy_lengths)
+                .sum();
+            game.wallet.deposit_lengths(legacy_lengths);
+            game.catch_up_achievement_stats();
+            game.apply_offline_progress();
+
+            // Everyone pushes off from rest when the game is reopened
+            for swimmer in &mut game.swimmers {
+                swimmer.start();
+            }
+            game.offer_perks();
+            return Ok(game);
+        }
+
+        Ok(Self::start(
+            name_data,
+            perk_data,
+            achievement_data,
+            config,
+            keymap,
+            seed.map_or_else(GameRng::default, GameRng::seeded),
+        ))
+    }
+
+    /// Starts a new game without touching the save file, with the settings from `config.json`
+    ///
+    /// Games started from the same seed that are given the same ticks and
+    /// actions play out identically.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed for the game's random number generator, a random one if `None`
+    ///
+    /// # Returns
+    /// A Result containing the new Game or an error
+    pub fn new(seed: Option<u64>) -> Result<Self> {
+        Self::with_config(seed, load_config()?)
+    }
+
+    /// Starts a new game with the given settings, reading no file at all
+    ///
+    /// # Arguments
+    /// * `seed` - Seed for the game's random number generator, a random one if `None`
+    /// * `config` - The user settings to play with
+    ///
+    /// # Returns
+    /// A Result containing the new Game, or an error if the config's key bindings are invalid
+    pub fn with_config(seed: Option<u64>, config: Config) -> Result<Self> {
+        let keymap: Keymap = Keymap::with_overrides(&config.keys)?;
+        Ok(Self::start(
+            load_name_data()?,
+            load_perk_data()?,
+            load_achievement_data()?,
+            config,
+            keymap,
+            seed.map_or_else(GameRng::default, GameRng::seeded),
+        ))
+    }
 
+    /// Builds a new game with a single swimmer from the loaded data
+    ///
+    /// # Arguments
+    /// * `name_data` - Names for swimmers, meets and clubs
+    /// * `perk_data` - Every perk swimmers can learn
+    /// * `achievement_data` - Every achievement that can be unlocked
+    /// * `config` - The user settings
+    /// * `keymap` - The key bindings built from the config
+    /// * `rng` - The random number generator the game draws from
+    ///
+    /// # Returns
+    /// The new Game
+    fn start(
+        name_data: NameData,
+        perk_data: PerkData,
+        achievement_data: AchievementData,
+        config: Config,
+        keymap: Keymap,
+        mut rng: GameRng,
+    ) -> Self {
         // Generate a random name for the first swimmer
-        let first_name: String = generate_random_name(&name_data);
+        let first_name: String = generate_random_name(&name_data, &mut rng);
 
         // Start with just one swimmer with reduced base speed of 0.7
-        let swimmers: Vec<Swimmer> = vec![Swimmer::new(&first_name, 0.7_f64)];
+        let swimmers: Vec<Swimmer> = vec![Swimmer::new(&first_name, 0.7_f64, &mut rng)];
+        let league: League = League::new(&name_data, &mut rng);
 
-        Ok(Self {
+        Self {
             swimmers,
             selected_index: 0_usize,
             name_data,
+            perk_data,
+            achievement_data,
             new_swimmer_cost: 25_usize, // Initial cost to add a new swimmer
-        })
+            wallet: Wallet::default(),
+            shop: Shop::default(),
+            prestige: Prestige::default(),
+            meet: None,
+            ticks_since_meet: 0_u64,
+            medals: Medals::default(),
+            league,
+            relays: Vec::new(),
+            pool: Pool::default(),
+            achievements: Achievements::default(),
+            rng,
+            saved_at: 0_u64,
+            offline_progress: None,
+            config,
+            notifications: Notifications::new(keymap.clone()),
+            keymap,
+            events: EventBus::default(),
+            screen: Screen::Pool,
+            ticks_since_sample: 0_u64,
+            entrants: Vec::new(),
+            race: None,
+            relay_picks: Vec::new(),
+            page_size: 1_usize,
+            unsimulated: Duration::ZERO,
+        }
+    }
+
+    /// Lets the player restore a backup after the save file failed to load
+    ///
+    /// # Arguments
+    /// * `dir` - Directory the save files are kept in
+    /// * `report` - The diagnostic explaining why the save file failed to load
+    /// * `backup_count` - Number of backups that are kept
+    /// * `choose_backup` - Asks the player which backup to restore, again after a
+    ///   backup fails to load, with that backup left out
+    ///
+    /// # Returns
+    /// A Result containing the restored Game, or the original diagnostic if
+    /// there are no loadable backups or the player declines to restore one
+    fn restore_backup<F>(
+        dir: &Path,
+        report: miette::Report,
+        backup_count: usize,
+        mut choose_backup: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&[Backup]) -> Result<Option<usize>>,
+    {
+        let mut backups: Vec<Backup> = save::list_backups(dir, backup_count);
+        while !backups.is_empty() {
+            let Some(choice) = choose_backup(&backups)? else {
+                return Err(report);
+            };
+
+            // A backup that is corrupt or gone is dropped from the menu and the player picks again
+            let Ok(Some(game)) = save::load_save::<Game>(&backups[choice].path) else {
+                backups.remove(choice);
+                continue;
+            };
+
+            // Keep the corrupt save for inspection without rotating it into the backups
+            save::quarantine_corrupt_save(dir)?;
+            return Ok(game);
+        }
+        Err(report)
+    }
+
+    /// Credits the lengths swimmers would have completed since the last save
+    fn apply_offline_progress(&mut self) {
+        // Older saves have no timestamp, so there is nothing to credit
+        if self.saved_at == 0_u64 {
+            return;
+        }
+
+        let elapsed_secs: u64 = unix_timestamp().saturating_sub(self.saved_at);
+        let ticks: u64 = elapsed_secs * 1000_u64 / FRAME_DURATION_MS;
+        if ticks == 0_u64 {
+            return;
+        }
+
+        // Relay members take turns in their relay's lane, everyone else swims on their own
+        let modifiers: Modifiers = self.modifiers();
+        let mut lengths: Vec<u64> = vec![0_u64; self.swimmers.len()];
+        let mut relays: u64 = 0_u64;
+        for index in 0_usize..self.relays.len() {
+            relays += self.swim_relay_offline(index, ticks, &modifiers, &mut lengths);
+        }
+        let in_relay: Vec<usize> = self
+            .relays
+            .iter()
+            .flat_map(|relay: &Relay| relay.members.iter().copied())
+            .collect();
+        for (i, swimmer) in self.swimmers.iter_mut().enumerate() {
+            if !in_relay.contains(&i) {
+                lengths[i] = swimmer.swim_ticks(ticks, &modifiers);
+            }
+        }
+        let lengths_by_swimmer: Vec<(String, u64)> = self
+            .swimmers
+            .iter()
+            .zip(lengths)
+            .map(|(swimmer, lengths): (&Swimmer, u64)| (swimmer.name.clone(), lengths))
+            .collect();
+
+        // Bank everything the team earned while away
+        let total_lengths: u64 = lengths_by_swimmer.iter().map(|(_, lengths)| lengths).sum();
+        self.wallet.deposit_lengths(total_lengths);
+        if total_lengths > 0_u64 {
+            self.events.emit(GameEvent::LengthsCompleted {
+                lengths: total_lengths,
+            });
+        }
+        let relay_bonus_lengths: u64 = relays * RELAY_BONUS_LENGTHS * self.pool.length.scale();
+        self.wallet.deposit_lengths(relay_bonus_lengths);
+
+        // Counted straight away rather than announcing every relay swum while away
+        self.achievements.stats.relays += relays;
+
+        // The league carries on without the team's manager
+        let league_weeks: u64 = self.league.advance(ticks).min(MAX_OFFLINE_WEEKS);
+        for _ in 0_u64..league_weeks {
+            self.play_league_week();
+        }
+
+        // Nothing worth reporting if no swimmer finished a length and no meet was raced
+        if total_lengths == 0_u64 && league_weeks == 0_u64 {
+            return;
+        }
+
+        self.offline_progress = Some(OfflineProgress {
+            elapsed: Duration::from_secs(elapsed_secs),
+            lengths_by_swimmer,
+            league_weeks,
+            relays,
+            relay_bonus_lengths,
+        });
+    }
+
+    /// Swims a relay's legs in turn for the time the game was closed
+    ///
+    /// The member in the water swims their leg while the others get their
+    /// energy back on deck, as `step` does one tick at a time. Once the team
+    /// starts a relay the same way as one of the last few, every relay after it
+    /// goes the same way too, so the repeats are counted in one step.
+    ///
+    /// # Arguments
+    /// * `index` - Index of the relay
+    /// * `ticks` - Number of ticks the game was closed for
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    /// * `lengths` - Lengths earned by each of the team's swimmers, added to
+    ///
+    /// # Returns
+    /// The number of relays completed
+    fn swim_relay_offline(
+        &mut self,
+        index: usize,
+        ticks: u64,
+        modifiers: &Modifiers,
+        lengths: &mut [u64],
+    ) -> u64 {
+        let members: Vec<usize> = self.relays[index].members.clone();
+        let mut remaining: u64 = ticks;
+        let mut completed: u64 = 0_u64;
+
+        let mut starts: Vec<RelayStart> = Vec::new();
+        while remaining > 0_u64 {
+            let relay: &Relay = &self.relays[index];
+            if relay.leg() == 1_usize && relay.ticks() == 0_u64 {
+                let start: RelayStart = RelayStart {
+                    members: members
+                        .iter()
+                        .map(|member: &usize| self.swimmers[*member].cycle_state())
+                        .collect(),
+                    ticks_left: remaining,
+                    completed,
+                };
+
+                // Energy settles towards a balance between legs, so it only needs to match within rounding
+                let repeat: Option<&RelayStart> = starts.iter().find(|earlier: &&RelayStart| {
+                    earlier.members.iter().zip(&start.members).all(
+                        |((earlier_energy, earlier_cycle), (energy, cycle))| {
+                            (earlier_energy - energy).abs() < 1e-9_f64 && earlier_cycle == cycle
+                        },
+                    )
+                });
+
+                // Everything from that start to this one happens again for as long as there is time
+                if let Some(earlier) = repeat {
+                    let period_ticks: u64 = earlier.ticks_left - remaining;
+                    let period_relays: u64 = completed - earlier.completed;
+                    let repeats: u64 = remaining / period_ticks;
+                    for member in &members {
+                        lengths[*member] += self.swimmers[*member]
+                            .repeat_lengths(LEG_LENGTHS * period_relays * repeats, modifiers);
+                    }
+                    remaining -= repeats * period_ticks;
+                    completed += repeats * period_relays;
+                    self.relays[index].completed += repeats * period_relays;
+                    starts.clear();
+                    continue;
+                }
+
+                starts.push(start);
+                if starts.len() > RELAY_STARTS_KEPT {
+                    starts.remove(0_usize);
+                }
+            }
+
+            // Swim to the end of the leg, or for whatever time is left
+            let active: usize = self.relays[index].active_member();
+            let to_swim: u64 = self.relays[index].lengths_to_hand_over();
+            let leg_ticks: u64 = self.swimmers[active]
+                .ticks_to_swim(to_swim, modifiers)
+                .map_or(remaining, |leg_ticks: u64| leg_ticks.min(remaining));
+            let start: u64 = self.swimmers[active].lifetime_lengths;
+            lengths[active] += self.swimmers[active].swim_ticks(leg_ticks, modifiers);
+            for member in &members {
+                if *member != active {
+                    self.swimmers[*member].recover(leg_ticks);
+                }
+            }
+            remaining -= leg_ticks;
+
+            // Lifetime lengths count 25 m lengths, legs are swum in lengths of the pool
+            let swum: u64 =
+                (self.swimmers[active].lifetime_lengths - start) / modifiers.length_scale;
+            match self.relays[index].record_ticks(leg_ticks, swum) {
+                Handover::Swimming => {}
+                Handover::NextLeg(next) => {
+                    self.swimmers[active].line_up();
+                    self.swimmers[next].line_up();
+                }
+                Handover::Finished(_) => {
+                    self.swimmers[active].line_up();
+                    let first: usize = self.relays[index].active_member();
+                    self.swimmers[first].line_up();
+                    completed += 1_u64;
+                }
+            }
+        }
+        completed
+    }
+
+    /// Records the current time and writes the game to the save file
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    pub fn save(&mut self) -> Result<()> {
+        self.saved_at = unix_timestamp();
+        save::write_save(Path::new(SAVE_DIR), self)
+    }
+
+    /// Takes the summary of the progress credited for the time the game was closed
+    ///
+    /// # Returns
+    /// The summary, or `None` if there was nothing to credit or it was already taken
+    pub fn take_offline_progress(&mut self) -> Option<OfflineProgress> {
+        self.offline_progress.take()
+    }
+
+    /// Advances the simulation by a span of time
+    ///
+    /// The simulation runs in fixed ticks of `FRAME_DURATION_MS`, so the time is
+    /// spent one whole tick at a time and the remainder carried over to the next call.
+    ///
+    /// # Arguments
+    /// * `elapsed` - Time since the game was last advanced
+    pub fn tick(&mut self, elapsed: Duration) {
+        let tick_duration: Duration = Duration::from_millis(FRAME_DURATION_MS);
+        self.unsimulated += elapsed;
+        while self.unsimulated >= tick_duration {
+            self.unsimulated -= tick_duration;
+            self.step();
+        }
+        self.notifications.advance(elapsed);
+    }
+
+    /// Carries out an action from the player on the screen they are looking at
+    ///
+    /// # Arguments
+    /// * `action` - The action to carry out
+    ///
+    /// # Returns
+    /// `true` if the player asked to quit
+    pub fn apply(&mut self, action: Action) -> bool {
+        let mut quit: bool = false;
+        match self.screen {
+            Screen::Pool => {
+                quit = self.handle_pool_action(action);
+            }
+            Screen::Upgrade {
+                selected_index,
+                retrain_to,
+            } => {
+                self.handle_upgrade_action(action, selected_index, retrain_to);
+            }
+            Screen::Details { selected_index } => {
+                self.handle_details_action(action, selected_index);
+            }
+            Screen::Shop { selected_index } => {
+                self.handle_shop_action(action, selected_index);
+            }
+            Screen::Season { selected_index } => {
+                self.handle_season_action(action, selected_index);
+            }
+            Screen::Meet { selected_index } => {
+                self.handle_meet_action(action, selected_index);
+            }
+            Screen::Race => {
+                self.handle_race_action(action);
+            }
+            Screen::League => {
+                self.handle_league_action(action);
+            }
+            Screen::Relay { selected_index } => {
+                self.handle_relay_action(action, selected_index);
+            }
+            Screen::Facilities { selected_index } => {
+                self.handle_facilities_action(action, selected_index);
+            }
+            Screen::Achievements { selected_index } => {
+                self.handle_achievements_action(action, selected_index);
+            }
+        }
+        // Report what the action did before the next frame is drawn
+        self.dispatch_events();
+        quit
+    }
+
+    /// Tells the game how many swimmers fit on screen, which is how far a page moves the selection
+    ///
+    /// # Arguments
+    /// * `page_size` - Number of swimmers that fit on screen
+    pub fn set_page_size(&mut self, page_size: usize) {
+        self.page_size = page_size.max(1_usize);
+    }
+
+    /// Advances every swimmer by one tick and banks the lengths they complete
+    fn step(&mut self) {
+        let modifiers: Modifiers = self.modifiers();
+
+        // Swimmers away at a meet sit out of the pool until their heat is over
+        let racing: Vec<usize> = match &self.race {
+            Some(race) if !race.is_finished() => race
+                .racers()
+                .iter()
+                .filter_map(|racer| racer.team_index)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        // Relay members wait on deck getting their energy back until their leg comes up
+        let waiting: Vec<usize> = self
+            .relays
+            .iter()
+            .flat_map(|relay: &Relay| {
+                let active: usize = relay.active_member();
+                relay
+                    .members
+                    .iter()
+                    .copied()
+                    .filter(move |member: &usize| *member != active)
+            })
+            .collect();
+        let relay_starts: Vec<u64> = self
+            .relays
+            .iter()
+            .map(|relay: &Relay| self.swimmers[relay.active_member()].lifetime_lengths)
+            .collect();
+
+        let mut lengths: u64 = 0_u64;
+        let mut levelled_up: bool = false;
+        for (i, swimmer) in self.swimmers.iter_mut().enumerate() {
+            if racing.contains(&i) {
+                continue;
+            }
+            if waiting.contains(&i) {
+                swimmer.recover(1_u64);
+                continue;
+            }
+            let was_exhausted: bool = swimmer.energy() <= 0_f64;
+            let level: u32 = swimmer.level();
+            lengths += swimmer.swim(&modifiers);
+
+            if swimmer.level() > level {
+                self.events.emit(GameEvent::LevelledUp {
+                    swimmer: swimmer.name.clone(),
+                    level: swimmer.level(),
+                });
+                levelled_up = true;
+            }
+
+            // Warn once when a swimmer runs out of energy
+            if !was_exhausted && swimmer.energy() <= 0_f64 {
+                self.events.emit(GameEvent::Exhausted {
+                    swimmer: swimmer.name.clone(),
+                });
+            }
+        }
+        self.wallet.deposit_lengths(lengths);
+        if lengths > 0_u64 {
+            self.events.emit(GameEvent::LengthsCompleted { lengths });
+        }
+        self.hand_over_relays(&relay_starts);
+
+        if levelled_up {
+            self.offer_perks();
+        }
+
+        // Swim the heat in progress, paying out as soon as the last swimmer touches
+        if let Some(race) = &mut self.race {
+            if race.tick(&modifiers) {
+                self.finish_race();
+            }
+        }
+
+        // Race the league's weekly meet whenever one is due
+        for _ in 0_u64..self.league.advance(1_u64) {
+            self.play_league_week();
+        }
+
+        // Announce the next meet once the last one is far enough behind
+        if self.meet.is_none() {
+            self.ticks_since_meet += 1_u64;
+            if self.ticks_since_meet >= MEET_INTERVAL_TICKS {
+                let meet: Meet =
+                    Meet::announce(&self.name_data, self.prestige.seasons, &mut self.rng);
+                self.events.emit(GameEvent::MeetAnnounced {
+                    meet: meet.name.clone(),
+                });
+                self.meet = Some(meet);
+            }
+        }
+
+        // Sample everyone's speed at fixed intervals for the detail screen sparkline
+        self.ticks_since_sample += 1_u64;
+        if self.ticks_since_sample >= SPEED_SAMPLE_TICKS {
+            for swimmer in &mut self.swimmers {
+                swimmer.sample_speed(self.ticks_since_sample);
+            }
+            self.ticks_since_sample = 0_u64;
+        }
+
+        self.dispatch_events();
+    }
+
+    /// Counts what saves from before achievements already had towards them
+    fn catch_up_achievement_stats(&mut self) {
+        let season_lengths: u64 = self.season_lengths();
+        let stats = &mut self.achievements.stats;
+        stats.lengths = stats.lengths.max(season_lengths);
+        stats.team_size = stats.team_size.max(self.swimmers.len() as u64);
+        stats.medals = stats.medals.max(self.medals.total() as u64);
+    }
+
+    /// Hands every queued event to each subscriber, until no subscriber has anything left to say
+    ///
+    /// New systems that react to the game subscribe by being handed the events here.
+    fn dispatch_events(&mut self) {
+        loop {
+            let events: Vec<GameEvent> = self.events.take();
+            if events.is_empty() {
+                return;
+            }
+            for event in &events {
+                self.achievements.on_event(event);
+                self.notifications.on_event(event);
+            }
+            // Whatever the events counted towards may have unlocked achievements, announced in turn
+            self.unlock_achievements();
+        }
+    }
+
+    /// Unlocks every achievement reached since the last check and emits an event for each one
+    fn unlock_achievements(&mut self) {
+        for achievement in self.achievements.unlock(&self.achievement_data) {
+            self.events.emit(GameEvent::AchievementUnlocked {
+                name: achievement.name.clone(),
+                bonus: achievement.bonus.description(),
+            });
+        }
+    }
+
+    /// Moves every relay on to its next leg once the member in the water has swum theirs
+    ///
+    /// # Arguments
+    /// * `relay_starts` - Lifetime lengths of each relay's member in the water before the tick
+    fn hand_over_relays(&mut self, relay_starts: &[u64]) {
+        // Lifetime lengths count 25 m lengths, legs are swum in lengths of the pool
+        let length_scale: u64 = self.pool.length.scale();
+        let relay_bonus: u64 = RELAY_BONUS_LENGTHS * length_scale;
+
+        let mut bonus_lengths: u64 = 0_u64;
+        for (relay, start) in self.relays.iter_mut().zip(relay_starts) {
+            let active: usize = relay.active_member();
+            let lengths: u64 = (self.swimmers[active].lifetime_lengths - start) / length_scale;
+            match relay.record_ticks(1_u64, lengths) {
+                Handover::Swimming => {}
+                Handover::NextLeg(next) => {
+                    self.swimmers[active].line_up();
+                    self.swimmers[next].line_up();
+                }
+                Handover::Finished(ticks) => {
+                    self.swimmers[active].line_up();
+                    self.swimmers[relay.active_member()].line_up();
+                    bonus_lengths += relay_bonus;
+                    self.events.emit(GameEvent::RelayFinished {
+                        relay: relay.name.clone(),
+                        ticks,
+                        bonus_lengths: relay_bonus,
+                    });
+                }
+            }
+        }
+        self.wallet.deposit_lengths(bonus_lengths);
+    }
+
+    /// Rolls a perk offer for every swimmer with a level-up waiting and no offer yet
+    fn offer_perks(&mut self) {
+        for swimmer in &mut self.swimmers {
+            if swimmer.unclaimed_perks() > 0_u32 && swimmer.perk_offer().is_empty() {
+                let offer: Vec<Perk> = self.perk_data.roll(swimmer.perks(), &mut self.rng);
+                swimmer.offer_perks(offer);
+            }
+        }
+    }
+
+    /// Picks a perk for the selected swimmer and notifies the player of the result
+    ///
+    /// # Arguments
+    /// * `index` - Index of the perk in the selected swimmer's offer
+    fn choose_perk(&mut self, index: usize) {
+        let swimmer: &mut Swimmer = &mut self.swimmers[self.selected_index];
+        let name: String = swimmer.name.clone();
+
+        if let Some(perk) = swimmer.choose_perk(index) {
+            self.events.emit(GameEvent::PerkLearned {
+                swimmer: name,
+                perk: perk.name.clone(),
+            });
+            // Roll the next offer straight away if more level-ups are waiting
+            self.offer_perks();
+        }
+    }
+
+    /// Gets the swimmers in the team
+    pub fn swimmers(&self) -> &[Swimmer] {
+        &self.swimmers
+    }
+
+    /// Gets the index of the currently selected swimmer
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    /// Gets the cost of hiring the next swimmer
+    pub fn new_swimmer_cost(&self) -> usize {
+        self.new_swimmer_cost
+    }
+
+    /// Gets the team wallet
+    pub fn wallet(&self) -> &Wallet {
+        &self.wallet
+    }
+
+    /// Gets the permanent upgrades bought so far
+    pub fn shop(&self) -> &Shop {
+        &self.shop
+    }
+
+    /// Gets the team's home pool
+    pub fn pool(&self) -> &Pool {
+        &self.pool
+    }
+
+    /// Gets the achievements unlocked so far
+    pub fn achievements(&self) -> &Achievements {
+        &self.achievements
+    }
+
+    /// Gets every achievement that can be unlocked
+    pub fn achievement_list(&self) -> &[Achievement] {
+        &self.achievement_data.achievements
+    }
+
+    /// Gets the trophies and bonuses kept across seasons
+    pub fn prestige(&self) -> &Prestige {
+        &self.prestige
+    }
+
+    /// Gets the lifetime lengths of every swimmer in the current season
+    pub fn season_lengths(&self) -> u64 {
+        self.swimmers
+            .iter()
+            .map(|swimmer: &Swimmer| swimmer.lifetime_lengths)
+            .sum()
+    }
+
+    /// Gets the meet open for entries, if one has been announced
+    pub fn meet(&self) -> Option<&Meet> {
+        self.meet.as_ref()
+    }
+
+    /// Gets the swimmers picked to enter the open meet
+    pub fn entrants(&self) -> &[usize] {
+        &self.entrants
+    }
+
+    /// Gets the heat being swum or whose results are on screen
+    pub fn race(&self) -> Option<&Race> {
+        self.race.as_ref()
+    }
+
+    /// Gets the relay teams
+    pub fn relays(&self) -> &[Relay] {
+        &self.relays
+    }
+
+    /// Gets the swimmers picked for the relay being formed, in leg order
+    pub fn relay_picks(&self) -> &[usize] {
+        &self.relay_picks
+    }
+
+    /// Gets the league the team plays in
+    pub fn league(&self) -> &League {
+        &self.league
+    }
+
+    /// Gets the medals the team has won at meets
+    pub fn medals(&self) -> &Medals {
+        &self.medals
+    }
+
+    /// Gets the notifications currently on screen
+    pub fn notifications(&self) -> &Notifications {
+        &self.notifications
+    }
+
+    /// Gets the user settings
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Gets which keys ask for which action
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Gets the seed the game's random number generator was started from
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    /// Gets the screen the player is looking at
+    pub fn screen(&self) -> Screen {
+        self.screen
+    }
+
+    /// Gets the price of the next point, in lengths
+    pub fn point_price(&self) -> u64 {
+        self.wallet.point_price(&self.config.exchange)
+    }
+
+    /// Combines every permanent upgrade into the modifiers applied to swimmers
+    ///
+    /// # Returns
+    /// The current global modifiers
+    pub fn modifiers(&self) -> Modifiers {
+        let mut modifiers: Modifiers = Modifiers::default();
+        self.shop.apply(&mut modifiers);
+        self.prestige.apply(&mut modifiers);
+        self.pool.apply(&mut modifiers);
+        self.achievements
+            .apply(&self.achievement_data, &mut modifiers);
+        modifiers
+    }
+
+    /// Selects the swimmer at the given index, clamped to the last swimmer
+    ///
+    /// # Arguments
+    /// * `index` - Index of the swimmer to select
+    fn select(&mut self, index: usize) {
+        self.selected_index = index.min(self.swimmers.len() - 1_usize);
+    }
+
+    /// Upgrades one stat of the selected swimmer and notifies the player of the result
+    ///
+    /// # Arguments
+    /// * `stat` - The stat to upgrade
+    fn upgrade_selected_swimmer(&mut self, stat: Stat) {
+        let modifiers: Modifiers = self.modifiers();
+        let swimmer: &mut Swimmer = &mut self.swimmers[self.selected_index];
+
+        if swimmer.upgrade(stat, &mut self.wallet, &modifiers) {
+            self.events.emit(GameEvent::Upgraded {
+                swimmer: swimmer.name.clone(),
+                stat,
+                value: swimmer.display_stat(stat),
+            });
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: format!("{}'s {} upgrade", swimmer.name, stat.name()),
+                currency: Currency::Lengths,
+                needed: self
+                    .wallet
+                    .lengths_needed(swimmer.upgrade_price(stat, &modifiers)),
+            });
+        }
+    }
+
+    /// Retrains the selected swimmer into another stroke and notifies the player of the result
+    ///
+    /// # Arguments
+    /// * `stroke` - The stroke to switch to
+    ///
+    /// # Returns
+    /// `true` if the swimmer was retrained
+    fn retrain_selected_swimmer(&mut self, stroke: Stroke) -> bool {
+        let modifiers: Modifiers = self.modifiers();
+        let swimmer: &mut Swimmer = &mut self.swimmers[self.selected_index];
+
+        if swimmer.retrain(stroke, &mut self.wallet, &modifiers) {
+            self.events.emit(GameEvent::Retrained {
+                swimmer: swimmer.name.clone(),
+                stroke,
+            });
+            true
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: format!("retraining {}", swimmer.name),
+                currency: Currency::Lengths,
+                needed: self
+                    .wallet
+                    .lengths_needed(swimmer.retrain_price(&modifiers)),
+            });
+            false
+        }
+    }
+
+    /// Benches the selected swimmer to rest, or sends them back into the pool
+    fn toggle_selected_swimmer_resting(&mut self) {
+        if let Some(relay) = self.relay_of(self.selected_index) {
+            self.events.emit(GameEvent::RelayMemberBusy {
+                swimmer: self.swimmers[self.selected_index].name.clone(),
+                relay: relay.name.clone(),
+                resting: true,
+            });
+            return;
+        }
+
+        let swimmer: &mut Swimmer = &mut self.swimmers[self.selected_index];
+        swimmer.toggle_resting();
+        self.events.emit(GameEvent::RestToggled {
+            swimmer: swimmer.name.clone(),
+            resting: swimmer.resting,
+        });
+    }
+
+    /// Opens the entry screen of the announced meet, or tells the player when the next one is due
+    fn open_meet(&mut self) {
+        if self.meet.is_none() {
+            let seconds: u64 = MEET_INTERVAL_TICKS.saturating_sub(self.ticks_since_meet)
+                * FRAME_DURATION_MS
+                / 1000_u64;
+            self.events.emit(GameEvent::MeetNotOpen { seconds });
+            return;
+        }
+
+        self.entrants.clear();
+        self.screen = Screen::Meet {
+            selected_index: self.selected_index,
+        };
+    }
+
+    /// Enters a swimmer into the open meet, or withdraws them if they are already entered
+    ///
+    /// # Arguments
+    /// * `index` - Index of the swimmer
+    fn toggle_entrant(&mut self, index: usize) {
+        if let Some(position) = self.entrants.iter().position(|entrant| *entrant == index) {
+            self.entrants.remove(position);
+        } else if self.entrants.len() >= MAX_ENTRANTS {
+            self.events.emit(GameEvent::MeetFull {
+                entrants: MAX_ENTRANTS,
+            });
+        } else {
+            self.entrants.push(index);
+        }
+    }
+
+    /// Starts the heat of the open meet with the picked entrants and switches to the race screen
+    fn start_race(&mut self) {
+        let Some(meet) = self.meet.clone() else {
+            return;
+        };
+        if self.entrants.is_empty() {
+            self.events.emit(GameEvent::NoEntrants);
+            return;
+        }
+
+        let entrants: Vec<(usize, &Swimmer)> = self
+            .entrants
+            .iter()
+            .map(|index: &usize| (*index, &self.swimmers[*index]))
+            .collect();
+        self.race = Some(Race::new(
+            meet,
+            &entrants,
+            &self.name_data,
+            &self.modifiers(),
+            &mut self.rng,
+        ));
+        self.screen = Screen::Race;
+    }
+
+    /// Pays out prize money and medals once the heat is over and closes the meet
+    fn finish_race(&mut self) {
+        let Some(race) = &self.race else {
+            return;
+        };
+
+        let mut prize_money: u64 = 0_u64;
+        let mut medals: u64 = 0_u64;
+        let mut best_place: usize = usize::MAX;
+        for (lane, place) in race.standings() {
+            let Some(index) = race.racers()[lane].team_index else {
+                continue;
+            };
+            if let Some(swimmer) = self.swimmers.get_mut(index) {
+                swimmer.record_race(place, &race.meet.name);
+            }
+            self.medals.award(place);
+            if place <= 3_usize {
+                medals += 1_u64;
+            }
+            prize_money += race.meet.prize_for(place);
+            best_place = best_place.min(place);
+        }
+        self.wallet.deposit_prize_money(prize_money);
+        self.events.emit(GameEvent::RaceFinished {
+            meet: race.meet.name.clone(),
+            best_place,
+            medals,
+            prize_money,
+        });
+
+        self.meet = None;
+        self.ticks_since_meet = 0_u64;
+    }
+
+    /// Races the league's weekly meet without the player, then ends the league season if it was the last
+    ///
+    /// The club sends its fastest swimmer who is not resting. With everyone on
+    /// the bench the club forfeits and finishes last.
+    fn play_league_week(&mut self) {
+        let Some(fixture) = self.league.next_fixture().cloned() else {
+            return;
+        };
+        let modifiers: Modifiers = self.modifiers();
+        let rivals: Vec<(String, f64)> = self.league.rivals();
+
+        let entrant: Option<(usize, &Swimmer)> = self
+            .swimmers
+            .iter()
+            .enumerate()
+            .filter(|(_, swimmer)| !swimmer.resting)
+            .max_by(|(_, a), (_, b)| {
+                a.effective_speed(&modifiers)
+                    .total_cmp(&b.effective_speed(&modifiers))
+            });
+        let results: Vec<String> = match entrant {
+            Some(entrant) => {
+                let mut race: Race = Race::with_rivals(
+                    fixture.clone(),
+                    &[entrant],
+                    &rivals,
+                    &self.name_data,
+                    &mut self.rng,
+                );
+                race.run(&modifiers);
+                race.standings()
+                    .into_iter()
+                    .map(|(lane, _)| race.racers()[lane].club.clone())
+                    .collect()
+            }
+            None => rivals
+                .into_iter()
+                .map(|(club, _)| club)
+                .chain([TEAM_CLUB.to_string()])
+                .collect(),
+        };
+        let place: usize = results
+            .iter()
+            .position(|club: &String| club == TEAM_CLUB)
+            .map_or(results.len(), |index: usize| index + 1_usize);
+
+        self.league.record_week(&results, &mut self.rng);
+        let prize_money: u64 = fixture.prize_for(place);
+        self.wallet.deposit_prize_money(prize_money);
+
+        self.events.emit(GameEvent::LeagueWeekRaced {
+            week: self.league.week,
+            meet: fixture.name,
+            place,
+            prize_money,
+        });
+
+        if self.league.is_season_over() {
+            let outcome: SeasonOutcome = self.league.end_season(&self.name_data, &mut self.rng);
+            self.events.emit(GameEvent::LeagueSeasonEnded {
+                outcome,
+                division: self.league.player_division(),
+            });
+        }
+    }
+
+    /// Gets the relay a swimmer belongs to, if any
+    ///
+    /// # Arguments
+    /// * `index` - Index of the swimmer
+    ///
+    /// # Returns
+    /// The swimmer's relay, or `None` if they swim on their own
+    fn relay_of(&self, index: usize) -> Option<&Relay> {
+        self.relays
+            .iter()
+            .find(|relay: &&Relay| relay.members.contains(&index))
+    }
+
+    /// Disbands the selected swimmer's relay, or opens the relay screen to form a new one
+    fn open_relay(&mut self) {
+        if let Some(position) = self
+            .relays
+            .iter()
+            .position(|relay: &Relay| relay.members.contains(&self.selected_index))
+        {
+            let relay: Relay = self.relays.remove(position);
+            for member in &relay.members {
+                self.swimmers[*member].line_up();
+            }
+            self.events
+                .emit(GameEvent::RelayDisbanded { relay: relay.name });
+            return;
+        }
+
+        let available: usize = (0_usize..self.swimmers.len())
+            .filter(|index: &usize| self.relay_of(*index).is_none())
+            .count();
+        if available < RELAY_SIZE {
+            self.events.emit(GameEvent::RelayShort {
+                missing: RELAY_SIZE - available,
+                picked: false,
+            });
+            return;
+        }
+
+        self.relay_picks = vec![self.selected_index];
+        self.screen = Screen::Relay {
+            selected_index: self.selected_index,
+        };
+    }
+
+    /// Picks a swimmer for the relay being formed, or drops them if they were already picked
+    ///
+    /// # Arguments
+    /// * `index` - Index of the swimmer
+    fn toggle_relay_pick(&mut self, index: usize) {
+        if let Some(position) = self.relay_picks.iter().position(|pick| *pick == index) {
+            self.relay_picks.remove(position);
+        } else if let Some(relay) = self.relay_of(index) {
+            self.events.emit(GameEvent::RelayMemberBusy {
+                swimmer: self.swimmers[index].name.clone(),
+                relay: relay.name.clone(),
+                resting: false,
+            });
+        } else if self.relay_picks.len() >= RELAY_SIZE {
+            self.events.emit(GameEvent::RelayFull { legs: RELAY_SIZE });
+        } else {
+            self.relay_picks.push(index);
+        }
+    }
+
+    /// Forms a relay from the picked swimmers and goes back to the pool
+    fn form_relay(&mut self) {
+        if self.relay_picks.len() < RELAY_SIZE {
+            self.events.emit(GameEvent::RelayShort {
+                missing: RELAY_SIZE - self.relay_picks.len(),
+                picked: true,
+            });
+            return;
+        }
+
+        // Name the relay after the first letter no other relay uses
+        let name: String = ('A'..='Z')
+            .map(|letter: char| format!("Relay {}", letter))
+            .find(|name: &String| self.relays.iter().all(|relay: &Relay| relay.name != *name))
+            .unwrap_or_else(|| format!("Relay {}", self.relays.len() + 1_usize));
+
+        let members: Vec<usize> = std::mem::take(&mut self.relay_picks);
+        for member in &members {
+            let swimmer: &mut Swimmer = &mut self.swimmers[*member];
+            swimmer.resting = false;
+            swimmer.line_up();
+        }
+
+        self.events.emit(GameEvent::RelayFormed {
+            relay: name.clone(),
+            leg_lengths: LEG_LENGTHS,
+        });
+        self.relays.push(Relay::new(name, members));
+        self.screen = Screen::Pool;
+    }
+
+    /// Adds a new swimmer and notifies the player of the result
+    fn hire_swimmer(&mut self) {
+        // Every swimmer needs a lane to train in
+        if self.swimmers.len() >= self.pool.lanes() {
+            self.events.emit(GameEvent::LanesFull {
+                lanes: self.pool.lanes(),
+            });
+            return;
+        }
+
+        if self.add_new_swimmer() {
+            let swimmer: &Swimmer = &self.swimmers[self.swimmers.len() - 1_usize];
+            self.events.emit(GameEvent::SwimmerHired {
+                swimmer: swimmer.name.clone(),
+                speed: swimmer.speed,
+                team_size: self.swimmers.len(),
+            });
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: "a new swimmer".to_string(),
+                currency: Currency::Lengths,
+                needed: self.wallet.lengths_needed(self.new_swimmer_cost as u64),
+            });
+        }
+    }
+
+    /// Converts the team's lengths into points and notifies the player of the result
+    fn convert_lengths(&mut self) {
+        let point_price: u64 = self.point_price();
+        let points: u64 = self.wallet.convert_to_points(&self.config.exchange);
+
+        if points > 0_u64 {
+            self.events.emit(GameEvent::LengthsConverted { points });
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: "a point".to_string(),
+                currency: Currency::Lengths,
+                needed: self.wallet.lengths_needed(point_price),
+            });
+        }
+    }
+
+    /// Buys the next level of a permanent upgrade and notifies the player of the result
+    ///
+    /// # Arguments
+    /// * `item` - The shop item to buy
+    fn buy_shop_item(&mut self, item: ShopItem) {
+        let price: u64 = self.shop.price(item);
+
+        if self.shop.buy(item, &mut self.wallet) {
+            self.events.emit(GameEvent::Purchased {
+                item: item.name(),
+                level: self.shop.level(item),
+            });
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: item.name().to_string(),
+                currency: Currency::Points,
+                needed: self.wallet.points_needed(price),
+            });
+        }
+    }
+
+    /// Buys the next level of a pool facility and notifies the player of the result
+    ///
+    /// # Arguments
+    /// * `facility` - The facility to buy
+    fn buy_facility(&mut self, facility: Facility) {
+        let price: u64 = self.pool.price(facility);
+
+        if self.pool.buy(facility, &mut self.wallet) {
+            self.events.emit(GameEvent::Purchased {
+                item: facility.name(),
+                level: self.pool.level(facility),
+            });
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: facility.name().to_string(),
+                currency: Currency::PrizeMoney,
+                needed: self.wallet.prize_money_needed(price),
+            });
+        }
+    }
+
+    /// Switches the team to the other pool length and notifies the player of the result
+    fn switch_pool_length(&mut self) {
+        let price: u64 = self.pool.switch_price();
+
+        if self.pool.switch_length(&mut self.wallet) {
+            self.events.emit(GameEvent::PoolLengthChanged {
+                length: self.pool.length,
+            });
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: format!("the {} pool", self.pool.length.other().name()),
+                currency: Currency::PrizeMoney,
+                needed: self.wallet.prize_money_needed(price),
+            });
+        }
+    }
+
+    /// Buys the next level of a prestige bonus and notifies the player of the result
+    ///
+    /// # Arguments
+    /// * `item` - The prestige item to buy
+    fn buy_prestige_item(&mut self, item: PrestigeItem) {
+        let price: u64 = self.prestige.price(item);
+
+        if self.prestige.buy(item) {
+            self.events.emit(GameEvent::Purchased {
+                item: item.name(),
+                level: self.prestige.level(item),
+            });
+        } else {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: item.name().to_string(),
+                currency: Currency::Trophies,
+                needed: price - self.prestige.trophies,
+            });
+        }
+    }
+
+    /// Retires the club for trophies and starts a new season from scratch
+    ///
+    /// Swimmers, the hiring cost, lengths and points are reset. Points shop
+    /// upgrades, prestige bonuses and prize money for the facilities are kept.
+    ///
+    /// # Returns
+    /// `true` if a new season started, `false` if the season has not earned a trophy yet
+    fn start_new_season(&mut self) -> bool {
+        let season_lengths: u64 = self.season_lengths();
+        let trophies: u64 = Prestige::trophies_for(season_lengths);
+
+        if trophies == 0_u64 {
+            self.events.emit(GameEvent::PurchaseFailed {
+                item: "a trophy".to_string(),
+                currency: Currency::LifetimeLengths,
+                needed: Prestige::lengths_for(1_u64).saturating_sub(season_lengths),
+            });
+            return false;
+        }
+
+        self.prestige.complete_season(trophies);
+
+        // Start again with a single swimmer, who benefits from any academy levels
+        let first_name: String = generate_random_name(&self.name_data, &mut self.rng);
+        let base_speed: f64 = 0.7_f64 + self.modifiers().hire_speed_bonus;
+        self.swimmers = vec![Swimmer::new(&first_name, base_speed, &mut self.rng)];
+        self.relays.clear();
+        self.selected_index = 0_usize;
+        self.new_swimmer_cost = 25_usize;
+        self.wallet.start_season();
+
+        self.events.emit(GameEvent::SeasonStarted {
+            season: self.prestige.seasons + 1_u32,
+            trophies,
+        });
+        true
     }
 
     /// Adds a new swimmer if player has enough lengths
@@ -45,18 +1505,14 @@ impl Game {
     /// # Returns
     /// `true` if successful, `false` otherwise
     fn add_new_swimmer(&mut self) -> bool {
-        // Check if player has enough lengths to add a new swimmer
-        let total_lengths: i32 = self.swimmers.iter().map(|s: &Swimmer| s.lengths).sum();
-
-        if total_lengths >= self.new_swimmer_cost as i32 {
-            // Deduct the cost from the first swimmer (could distribute this differently)
-            self.swimmers[0].lengths -= self.new_swimmer_cost as i32;
-
+        // Pay for the new swimmer from the team wallet if it can afford it
+        if self.wallet.spend_lengths(self.new_swimmer_cost as u64) {
             // Generate a random name
-            let new_name: String = generate_random_name(&self.name_data);
+            let new_name: String = generate_random_name(&self.name_data, &mut self.rng);
 
-            // Create a new swimmer with slower base speed of 0.7 (changed from 1)
-            let mut new_swimmer: Swimmer = Swimmer::new(&new_name, 0.7_f64);
+            // Create a new swimmer with slower base speed of 0.7, plus any scouting bonus
+            let base_speed: f64 = 0.7_f64 + self.modifiers().hire_speed_bonus;
+            let mut new_swimmer: Swimmer = Swimmer::new(&new_name, base_speed, &mut self.rng);
 
             // Set a basic upgrade cost
             new_swimmer.upgrade_cost = 10_usize;
@@ -73,100 +1529,595 @@ impl Game {
         false
     }
 
-    /// Runs the main game loop
+    /// Handles an action on the pool screen
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
     ///
     /// # Returns
-    /// A Result indicating success or failure
-    pub fn run(&mut self) -> Result<()> {
-        // Enable raw mode
-        enable_raw_mode().into_diagnostic()?;
+    /// `true` if the player asked to quit
+    fn handle_pool_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => {
+                return true;
+            }
+            Action::Toggle => {
+                self.screen = Screen::Upgrade {
+                    selected_index: 0_usize,
+                    retrain_to: self.swimmers[self.selected_index].stroke.next(),
+                };
+            }
+            Action::Hire => {
+                self.hire_swimmer();
+            }
+            Action::Rest => {
+                self.toggle_selected_swimmer_resting();
+            }
+            Action::Confirm => {
+                self.screen = Screen::Details {
+                    selected_index: 0_usize,
+                };
+            }
+            Action::Convert => {
+                self.convert_lengths();
+            }
+            Action::Shop => {
+                self.screen = Screen::Shop {
+                    selected_index: 0_usize,
+                };
+            }
+            Action::Season => {
+                self.screen = Screen::Season {
+                    selected_index: 0_usize,
+                };
+            }
+            Action::Meet => {
+                self.open_meet();
+            }
+            Action::League => {
+                self.screen = Screen::League;
+            }
+            Action::Relay => {
+                self.open_relay();
+            }
+            Action::Facilities => {
+                self.screen = Screen::Facilities {
+                    selected_index: 0_usize,
+                };
+            }
+            Action::Achievements => {
+                self.screen = Screen::Achievements {
+                    selected_index: 0_usize,
+                };
+            }
+            Action::SelectPrev => {
+                self.select(self.selected_index.saturating_sub(1_usize));
+            }
+            Action::SelectNext => {
+                self.select(self.selected_index + 1_usize);
+            }
+            Action::PageUp => {
+                self.select(self.selected_index.saturating_sub(self.page_size));
+            }
+            Action::PageDown => {
+                self.select(self.selected_index + self.page_size);
+            }
+            Action::SelectFirst => {
+                self.select(0_usize);
+            }
+            Action::SelectLast => {
+                self.select(usize::MAX);
+            }
+            _ => {}
+        }
+        false
+    }
 
-        // Display the initial UI before entering the game loop
-        ui::display_ui(&self.swimmers, self.selected_index, self.new_swimmer_cost)?;
+    /// Handles an action on the upgrade menu of the selected swimmer
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted entry
+    /// * `retrain_to` - Stroke offered when retraining
+    fn handle_upgrade_action(&mut self, action: Action, selected_index: usize, retrain_to: Stroke) {
+        let current_stroke: Stroke = self.swimmers[self.selected_index].stroke;
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Upgrade {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                    retrain_to,
+                };
+            }
+            Action::SelectNext => {
+                self.screen = Screen::Upgrade {
+                    selected_index: (selected_index + 1_usize).min(Stat::ALL.len()),
+                    retrain_to,
+                };
+            }
+            // Cycle through the other strokes, skipping the one already swum
+            Action::CyclePrev | Action::CycleNext if selected_index == Stat::ALL.len() => {
+                let step = if action == Action::CyclePrev {
+                    Stroke::previous
+                } else {
+                    Stroke::next
+                };
+                let mut stroke: Stroke = step(retrain_to);
+                if stroke == current_stroke {
+                    stroke = step(stroke);
+                }
+                self.screen = Screen::Upgrade {
+                    selected_index,
+                    retrain_to: stroke,
+                };
+            }
+            Action::Confirm | Action::Toggle => match Stat::ALL.get(selected_index) {
+                Some(stat) => self.upgrade_selected_swimmer(*stat),
+                None => {
+                    if self.retrain_selected_swimmer(retrain_to) {
+                        self.screen = Screen::Upgrade {
+                            selected_index,
+                            retrain_to: retrain_to.next(),
+                        };
+                    }
+                }
+            },
+            Action::Back | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
+        }
+    }
 
-        let mut quit: bool = false;
-        // Use a fixed time step for smoother animation
-        let frame_duration: Duration = Duration::from_millis(33_u64); // ~30 FPS
-        let mut last_update: Instant = Instant::now();
-
-        // Use a fixed time step for rendering to avoid excessive updates
-        let render_duration: Duration = Duration::from_millis(100_u64); // ~10 FPS
-        let mut last_render: Instant = Instant::now();
-
-        while !quit {
-            let now: Instant = Instant::now();
-            let elapsed: Duration = now.duration_since(last_update);
-
-            // Only update the game state at fixed intervals
-            if elapsed >= frame_duration {
-                for swimmer in &mut self.swimmers {
-                    swimmer.swim();
+    /// Handles an action on the detail screen of the selected swimmer
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted perk on offer
+    fn handle_details_action(&mut self, action: Action, selected_index: usize) {
+        let offered: usize = self.swimmers[self.selected_index].perk_offer().len();
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Details {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                };
+            }
+            Action::SelectNext => {
+                self.screen = Screen::Details {
+                    selected_index: (selected_index + 1_usize).min(offered.saturating_sub(1_usize)),
+                };
+            }
+            Action::Confirm | Action::Toggle => {
+                self.choose_perk(selected_index);
+                self.screen = Screen::Details {
+                    selected_index: 0_usize,
+                };
+            }
+            Action::Back | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles an action on the points shop screen
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted shop item
+    fn handle_shop_action(&mut self, action: Action, selected_index: usize) {
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Shop {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                };
+            }
+            Action::SelectNext => {
+                self.screen = Screen::Shop {
+                    selected_index: (selected_index + 1_usize).min(ShopItem::ALL.len() - 1_usize),
+                };
+            }
+            Action::Confirm | Action::Toggle => {
+                self.buy_shop_item(ShopItem::ALL[selected_index]);
+            }
+            Action::Convert => {
+                self.convert_lengths();
+            }
+            Action::Back | Action::Shop | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles an action on the facilities screen
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted entry
+    fn handle_facilities_action(&mut self, action: Action, selected_index: usize) {
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Facilities {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                };
+            }
+            Action::SelectNext => {
+                self.screen = Screen::Facilities {
+                    selected_index: (selected_index + 1_usize).min(Facility::ALL.len()),
+                };
+            }
+            Action::Confirm | Action::Toggle => {
+                if selected_index < Facility::ALL.len() {
+                    self.buy_facility(Facility::ALL[selected_index]);
+                } else {
+                    self.switch_pool_length();
                 }
-                last_update = now;
-            }
-
-            // Only render the UI at fixed intervals
-            if now.duration_since(last_render) >= render_duration {
-                ui::display_ui(&self.swimmers, self.selected_index, self.new_swimmer_cost)?;
-                last_render = now;
-            }
-
-            // Handle user input with a shorter timeout to be responsive
-            if event::poll(Duration::from_millis(10_u64)).into_diagnostic()? {
-                if let Event::Key(event) = read().into_diagnostic()? {
-                    match event.code {
-                        KeyCode::Char('q') => {
-                            quit = true;
-                        }
-                        KeyCode::Char(' ') => {
-                            let swimmer: &mut Swimmer = &mut self.swimmers[self.selected_index];
-                            let success: bool = swimmer.upgrade();
-                            ui::show_upgrade_message(swimmer, success)?;
-                            thread::sleep(Duration::from_millis(800_u64));
-                        }
-                        KeyCode::Char('n') => {
-                            let success: bool = self.add_new_swimmer();
-                            if success {
-                                ui::show_new_swimmer_message(
-                                    &self.swimmers.last().unwrap(),
-                                    true,
-                                    None,
-                                )?;
-                            } else {
-                                ui::show_new_swimmer_message(
-                                    &self.swimmers[0],
-                                    false,
-                                    Some(self.new_swimmer_cost),
-                                )?;
-                            }
-                            thread::sleep(Duration::from_millis(800_u64));
-                        }
-                        KeyCode::Up => {
-                            if self.selected_index > 0_usize {
-                                self.selected_index -= 1_usize;
-                            }
-                        }
-                        KeyCode::Down => {
-                            if self.selected_index < self.swimmers.len() - 1_usize {
-                                self.selected_index += 1_usize;
-                            }
-                        }
-                        _ => {}
+            }
+            Action::Back | Action::Facilities | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles an action on the achievements screen
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted achievement
+    fn handle_achievements_action(&mut self, action: Action, selected_index: usize) {
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Achievements {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                };
+            }
+            Action::SelectNext => {
+                let last: usize = self
+                    .achievement_data
+                    .achievements
+                    .len()
+                    .saturating_sub(1_usize);
+                self.screen = Screen::Achievements {
+                    selected_index: (selected_index + 1_usize).min(last),
+                };
+            }
+            Action::Back | Action::Achievements | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles an action on the season screen
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted entry
+    fn handle_season_action(&mut self, action: Action, selected_index: usize) {
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Season {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                };
+            }
+            Action::SelectNext => {
+                self.screen = Screen::Season {
+                    selected_index: (selected_index + 1_usize).min(PrestigeItem::ALL.len()),
+                };
+            }
+            Action::Confirm | Action::Toggle => match PrestigeItem::ALL.get(selected_index) {
+                Some(item) => self.buy_prestige_item(*item),
+                None => {
+                    if self.start_new_season() {
+                        self.screen = Screen::Pool;
                     }
                 }
-            } else {
-                thread::sleep(Duration::from_millis(1_u64));
+            },
+            Action::Back | Action::Season | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles an action on the entry screen of the open meet
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted swimmer
+    fn handle_meet_action(&mut self, action: Action, selected_index: usize) {
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Meet {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                };
+            }
+            Action::SelectNext => {
+                self.screen = Screen::Meet {
+                    selected_index: (selected_index + 1_usize).min(self.swimmers.len() - 1_usize),
+                };
+            }
+            Action::Toggle => {
+                self.toggle_entrant(selected_index);
+            }
+            Action::Confirm => {
+                self.start_race();
+            }
+            Action::Back | Action::Meet | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles an action on the race screen, which can only be left once the heat is over
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    fn handle_race_action(&mut self, action: Action) {
+        let finished: bool = self.race.as_ref().is_none_or(Race::is_finished);
+        if finished && matches!(action, Action::Confirm | Action::Back | Action::Quit) {
+            self.race = None;
+            self.screen = Screen::Pool;
+        }
+    }
+
+    /// Handles an action on the league screen
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    fn handle_league_action(&mut self, action: Action) {
+        if matches!(action, Action::Back | Action::League | Action::Quit) {
+            self.screen = Screen::Pool;
+        }
+    }
+
+    /// Handles an action on the relay screen
+    ///
+    /// # Arguments
+    /// * `action` - The action to handle
+    /// * `selected_index` - Index of the highlighted swimmer
+    fn handle_relay_action(&mut self, action: Action, selected_index: usize) {
+        match action {
+            Action::SelectPrev => {
+                self.screen = Screen::Relay {
+                    selected_index: selected_index.saturating_sub(1_usize),
+                };
+            }
+            Action::SelectNext => {
+                self.screen = Screen::Relay {
+                    selected_index: (selected_index + 1_usize).min(self.swimmers.len() - 1_usize),
+                };
+            }
+            Action::Toggle => {
+                self.toggle_relay_pick(selected_index);
+            }
+            Action::Confirm => {
+                self.form_relay();
             }
+            Action::Back | Action::Relay | Action::Quit => {
+                self.screen = Screen::Pool;
+            }
+            _ => {}
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Starts a game with the default settings, whatever directory the tests run from
+    fn headless_game(seed: u64) -> Game {
+        Game::with_config(Some(seed), Config::default()).expect("the default settings are valid")
+    }
+
+    #[test]
+    fn ticking_earns_lengths() {
+        let mut game: Game = headless_game(1_u64);
+
+        game.tick(Duration::from_secs(60_u64));
+
+        assert!(game.wallet().lengths > 0_u64);
+        assert_eq!(game.wallet().lengths, game.swimmers()[0].lifetime_lengths);
+    }
+
+    #[test]
+    fn hiring_waits_for_the_lengths() {
+        let mut game: Game = headless_game(2_u64);
+
+        game.apply(Action::Hire);
+        assert_eq!(game.swimmers().len(), 1_usize);
+
+        while game.wallet().lengths < game.new_swimmer_cost() as u64 {
+            game.tick(Duration::from_secs(1_u64));
+        }
+        let lengths: u64 = game.wallet().lengths - game.new_swimmer_cost() as u64;
+        game.apply(Action::Hire);
+
+        assert_eq!(game.swimmers().len(), 2_usize);
+        assert_eq!(game.wallet().lengths, lengths);
+    }
+
+    /// Plays a game from a seed with the same ticks and hires every time
+    fn play(seed: u64) -> Game {
+        let mut game: Game = headless_game(seed);
+        for _ in 0_u32..6_u32 {
+            game.tick(Duration::from_secs(45_u64));
+            game.apply(Action::Hire);
+        }
+        game
+    }
+
+    /// Writes a game as the save file does and reads it back
+    fn round_trip(game: &Game) -> Game {
+        let saved: String = serde_json::to_string(game).expect("a game can be saved");
+        serde_json::from_str::<Game>(&saved).expect("a saved game can be read back")
+    }
+
+    #[test]
+    fn same_seed_plays_out_the_same() {
+        let (first, second): (Game, Game) = (play(42_u64), play(42_u64));
+
+        assert!(first.swimmers().len() > 1_usize);
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+        assert_ne!(
+            serde_json::to_string(first.swimmers()).unwrap(),
+            serde_json::to_string(play(43_u64).swimmers()).unwrap()
+        );
+    }
+
+    #[test]
+    fn resumed_game_carries_on_the_same_stream() {
+        let mut game: Game = play(5_u64);
+        let mut resumed: Game =
+            Game::resume(Some(round_trip(&game)), Some(5_u64), Config::default()).unwrap();
+
+        assert_eq!(resumed.seed(), 5_u64);
+        assert_eq!(resumed.rng.next_u64(), game.rng.next_u64());
+    }
+
+    #[test]
+    fn resuming_with_another_seed_is_rejected() {
+        let game: Game = play(5_u64);
+
+        assert!(Game::resume(Some(round_trip(&game)), Some(6_u64), Config::default()).is_err());
+        assert!(Game::resume(Some(round_trip(&game)), None, Config::default()).is_ok());
+        let new_game: Game = Game::resume(None, Some(6_u64), Config::default()).unwrap();
+        assert_eq!(new_game.seed(), 6_u64);
+    }
+
+    #[test]
+    fn corrupt_backups_are_left_out_until_one_loads() {
+        let dir: PathBuf = save::tests::scratch_dir("restore");
+        fs::write(dir.join("save.1.json"), "{ not json either").unwrap();
+        save::write_save(&dir, &play(8_u64)).unwrap();
+        fs::rename(dir.join(SAVE_PATH), dir.join("save.2.json")).unwrap();
+        fs::write(dir.join(SAVE_PATH), "{ not json").unwrap();
+
+        let Err(report) = save::load_save::<Game>(&dir.join(SAVE_PATH)) else {
+            panic!("the save file is corrupt");
+        };
+        let mut offered: Vec<usize> = Vec::new();
+        let game: Game = Game::restore_backup(&dir, report, 3_usize, |backups: &[Backup]| {
+            offered.push(backups.len());
+            Ok(Some(0_usize))
+        })
+        .unwrap();
+
+        assert_eq!(offered, vec![2_usize, 1_usize]);
+        assert_eq!(game.seed(), 8_u64);
+        assert!(!dir.join(SAVE_PATH).exists());
+        assert!(dir.join("save.corrupt.json").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn declining_every_backup_keeps_the_corrupt_save() {
+        let dir: PathBuf = save::tests::scratch_dir("decline");
+        save::write_save(&dir, &play(8_u64)).unwrap();
+        fs::rename(dir.join(SAVE_PATH), dir.join("save.1.json")).unwrap();
+        fs::write(dir.join(SAVE_PATH), "{ not json").unwrap();
+
+        let Err(report) = save::load_save::<Game>(&dir.join(SAVE_PATH)) else {
+            panic!("the save file is corrupt");
+        };
+        let restored: Result<Game> = Game::restore_backup(&dir, report, 3_usize, |_| Ok(None));
+
+        assert!(restored.is_err());
+        assert!(dir.join(SAVE_PATH).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn actions_follow_the_screen() {
+        let mut game: Game = headless_game(3_u64);
+
+        game.apply(Action::Shop);
+        assert!(matches!(game.screen(), Screen::Shop { .. }));
+        assert!(!game.apply(Action::Quit));
+        assert!(game.screen() == Screen::Pool);
+        assert!(game.apply(Action::Quit));
+    }
+
+    /// Starts a game with a relay of the first four swimmers and a fifth swimmer training alone
+    fn relay_game(seed: u64) -> Game {
+        let mut game: Game = headless_game(seed);
+        for (name, speed) in [
+            ("Second", 2_f64),
+            ("Third", 4_f64),
+            ("Fourth", 3_f64),
+            ("Solo", 3_f64),
+        ] {
+            let swimmer: Swimmer = Swimmer::new(name, speed, &mut game.rng);
+            game.swimmers.push(swimmer);
+        }
+        game.relay_picks = vec![0_usize, 1_usize, 2_usize, 3_usize];
+        game.form_relay();
+        game.events.take();
+        game
+    }
+
+    #[test]
+    fn relays_swum_offline_match_relays_swum_online() {
+        let mut online: Game = relay_game(9_u64);
+        let mut offline: Game =
+            Game::resume(Some(round_trip(&online)), Some(9_u64), Config::default()).unwrap();
+        let elapsed_secs: u64 = 1_800_u64;
+
+        // Achievements unlocked on the way would speed the team up online only
+        online.achievement_data = AchievementData::default();
+
+        online.tick(Duration::from_millis(
+            elapsed_secs * 1000_u64 / FRAME_DURATION_MS * FRAME_DURATION_MS,
+        ));
+        offline.saved_at = unix_timestamp() - elapsed_secs;
+        offline.apply_offline_progress();
+
+        // Enough relays to settle into a repeat that is counted in one step
+        let relays: u64 = online.relays()[0_usize].completed;
+        assert!(relays > 2_u64 * RELAY_STARTS_KEPT as u64);
+        assert_eq!(offline.relays()[0_usize].completed, relays);
+        for (swum_online, swum_offline) in online.swimmers().iter().zip(offline.swimmers()) {
+            assert_eq!(swum_offline.lifetime_lengths, swum_online.lifetime_lengths);
+            assert!((swum_offline.energy() - swum_online.energy()).abs() < 1e-6_f64);
+        }
+        assert_eq!(offline.wallet().lengths, online.wallet().lengths);
+
+        // Each member only swims their own legs, while the solo swimmer swims the whole time
+        let solo: u64 = offline.swimmers()[4_usize].lifetime_lengths;
+        for member in &offline.swimmers()[..RELAY_SIZE] {
+            assert!(member.lifetime_lengths * 2_u64 < solo);
+        }
+    }
 
-        // Show goodbye message
-        ui::show_goodbye_message()?;
+    #[test]
+    fn relays_swum_offline_are_reported_and_counted() {
+        let mut game: Game = relay_game(10_u64);
+        game.saved_at = unix_timestamp() - 600_u64;
 
-        // Wait for final keypress
-        read().into_diagnostic()?;
+        game.apply_offline_progress();
 
-        // Disable raw mode before exiting
-        disable_raw_mode().into_diagnostic()?;
-        Ok(())
+        let relays: u64 = game.relays()[0_usize].completed;
+        let progress: OfflineProgress = game
+            .take_offline_progress()
+            .expect("the team swam while away");
+        assert!(relays > 0_u64);
+        assert_eq!(progress.relays, relays);
+        assert_eq!(progress.relay_bonus_lengths, relays * RELAY_BONUS_LENGTHS);
+        assert_eq!(game.achievements.stats.relays, relays);
+        let earned: u64 = progress
+            .lengths_by_swimmer
+            .iter()
+            .map(|(_, lengths)| lengths)
+            .sum();
+        assert_eq!(game.wallet().lengths, earned + progress.relay_bonus_lengths);
     }
 }
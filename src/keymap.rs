@@ -0,0 +1,356 @@
+use crate::action::Action;
+use miette::Result;
+use std::collections::BTreeMap;
+
+/// Keys with a name rather than a character, as written in the config
+const NAMED_KEYS: [&str; 13] = [
+    "up",
+    "down",
+    "left",
+    "right",
+    "pageup",
+    "pagedown",
+    "home",
+    "end",
+    "enter",
+    "space",
+    "esc",
+    "tab",
+    "backspace",
+];
+
+/// Modifiers a key can be held with, written before it as in `ctrl+d`
+const MODIFIERS: [&str; 2] = ["ctrl", "alt"];
+
+/// Bindings every keymap starts from, arrows and Vim-style keys alike
+const DEFAULT_BINDINGS: [(&str, Action); 28] = [
+    ("up", Action::SelectPrev),
+    ("k", Action::SelectPrev),
+    ("down", Action::SelectNext),
+    ("j", Action::SelectNext),
+    ("pageup", Action::PageUp),
+    ("ctrl+u", Action::PageUp),
+    ("pagedown", Action::PageDown),
+    ("ctrl+d", Action::PageDown),
+    ("home", Action::SelectFirst),
+    ("g g", Action::SelectFirst),
+    ("end", Action::SelectLast),
+    ("G", Action::SelectLast),
+    ("left", Action::CyclePrev),
+    ("right", Action::CycleNext),
+    ("enter", Action::Confirm),
+    ("space", Action::Toggle),
+    ("esc", Action::Back),
+    ("q", Action::Quit),
+    ("n", Action::Hire),
+    ("b", Action::Rest),
+    ("c", Action::Convert),
+    ("p", Action::Shop),
+    ("r", Action::Season),
+    ("m", Action::Meet),
+    ("l", Action::League),
+    ("f", Action::Relay),
+    ("o", Action::Facilities),
+    ("a", Action::Achievements),
+];
+
+/// How the keys pressed so far match the keymap
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum KeyMatch {
+    Action(Action), // The keys complete a binding
+    Pending,        // The keys start a chord, wait for the next one
+    Unbound,        // No binding starts with the keys
+}
+
+/// A key, or a chord of keys pressed one after another, and the action it asks for
+#[derive(Clone)]
+struct Binding {
+    keys: Vec<String>, // Key names in the order they are pressed
+    action: Action,
+}
+
+/// Which keys ask the game for which action
+///
+/// Keys are named as in the config: a character such as `j` or `G`, a named
+/// key such as `space` or `pageup`, optionally held with a modifier such as
+/// `ctrl+d`. A chord is several keys separated by spaces, such as `g g`.
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>, // Earlier bindings are the ones shown in hints
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: DEFAULT_BINDINGS
+                .iter()
+                .map(|(keys, action)| Binding {
+                    keys: keys.split_whitespace().map(str::to_string).collect(),
+                    action: *action,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Builds the default keymap with the user's bindings on top
+    ///
+    /// # Arguments
+    /// * `overrides` - Action for each key or chord, `None` to unbind it
+    ///
+    /// # Returns
+    /// A Result containing the Keymap, or an error naming a key that does not exist
+    /// or a key that could never fire because a bound chord starts with it
+    pub fn with_overrides(overrides: &BTreeMap<String, Option<Action>>) -> Result<Self> {
+        let mut keymap: Keymap = Keymap::default();
+        for (chord, action) in overrides {
+            let keys: Vec<String> = parse_chord(chord)?;
+            keymap
+                .bindings
+                .retain(|binding: &Binding| binding.keys != keys);
+            if let Some(action) = action {
+                keymap.bindings.push(Binding {
+                    keys,
+                    action: *action,
+                });
+            }
+        }
+
+        // A key is read as the start of any chord beginning with it, so it could never fire
+        for binding in &keymap.bindings {
+            if let Some(chord) = keymap.bindings.iter().find(|other: &&Binding| {
+                other.keys.len() > binding.keys.len() && other.keys.starts_with(&binding.keys)
+            }) {
+                let chord: String = chord.keys.join(" ");
+                return Err(miette::miette!(
+                    help = format!(
+                        "Unbind the chord with \"{}\": null, or bind another key",
+                        chord
+                    ),
+                    "Key binding '{}' in config starts the chord '{}', so it would never fire",
+                    binding.keys.join(" "),
+                    chord
+                ));
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// Matches the keys pressed so far against the bindings
+    ///
+    /// A chord wins over a binding of its first keys alone, which is why
+    /// `with_overrides` refuses to bind both.
+    ///
+    /// # Arguments
+    /// * `pressed` - Names of the keys pressed since the last action, oldest first
+    ///
+    /// # Returns
+    /// The action the keys ask for, or whether they may still become one
+    pub fn resolve(&self, pressed: &[String]) -> KeyMatch {
+        let starts_chord: bool = self.bindings.iter().any(|binding: &Binding| {
+            binding.keys.len() > pressed.len() && binding.keys.starts_with(pressed)
+        });
+        if starts_chord {
+            return KeyMatch::Pending;
+        }
+
+        self.bindings
+            .iter()
+            .find(|binding: &&Binding| binding.keys == pressed)
+            .map_or(KeyMatch::Unbound, |binding: &Binding| {
+                KeyMatch::Action(binding.action)
+            })
+    }
+
+    /// Gets how the first key bound to an action is shown to the player
+    ///
+    /// # Arguments
+    /// * `action` - The action
+    ///
+    /// # Returns
+    /// The key's label, such as `↑`, `Space` or `gg`, or `None` if the action is unbound
+    pub fn label(&self, action: Action) -> Option<String> {
+        let binding: &Binding = self
+            .bindings
+            .iter()
+            .find(|binding: &&Binding| binding.action == action)?;
+        let labels: Vec<String> = binding.keys.iter().map(|key| key_label(key)).collect();
+        // Single characters read best run together, like Vim's `gg`
+        let separator: &str = if labels.iter().all(|label| label.chars().count() == 1) {
+            ""
+        } else {
+            " "
+        };
+        Some(labels.join(separator))
+    }
+
+    /// Builds a key hint such as `[↑/↓] Select` for one or more actions
+    ///
+    /// # Arguments
+    /// * `actions` - The actions, whose keys are shown separated by slashes
+    /// * `description` - What the keys do
+    ///
+    /// # Returns
+    /// The hint, or `None` if none of the actions is bound
+    pub fn hint(&self, actions: &[Action], description: &str) -> Option<String> {
+        let labels: Vec<String> = actions
+            .iter()
+            .filter_map(|action: &Action| self.label(*action))
+            .collect();
+        if labels.is_empty() {
+            return None;
+        }
+        Some(format!("[{}] {}", labels.join("/"), description))
+    }
+
+    /// Gets the bracketed label of the key bound to an action, for use inside a sentence
+    ///
+    /// # Arguments
+    /// * `action` - The action
+    ///
+    /// # Returns
+    /// The label, such as `[Enter]`, or `[unbound]` if no key asks for the action
+    pub fn key(&self, action: Action) -> String {
+        format!(
+            "[{}]",
+            self.label(action).unwrap_or_else(|| "unbound".to_string())
+        )
+    }
+}
+
+/// Splits a chord from the config into its key names, checking each one exists
+///
+/// # Arguments
+/// * `chord` - Key names separated by spaces, such as `g g` or `ctrl+d`
+///
+/// # Returns
+/// A Result containing the key names, or an error naming the first unknown key
+fn parse_chord(chord: &str) -> Result<Vec<String>> {
+    let keys: Vec<String> = chord.split_whitespace().map(str::to_string).collect();
+    if keys.is_empty() {
+        return Err(miette::miette!("Empty key binding in config"));
+    }
+
+    for key in &keys {
+        let base: &str = match key.split_once('+') {
+            Some((modifier, base)) if MODIFIERS.contains(&modifier) => base,
+            _ => key,
+        };
+        if base.chars().count() != 1 && !NAMED_KEYS.contains(&base) {
+            return Err(miette::miette!(
+                help = format!(
+                    "Use a single character or one of: {}",
+                    NAMED_KEYS.join(", ")
+                ),
+                "Unknown key '{}' in config",
+                key
+            ));
+        }
+    }
+    Ok(keys)
+}
+
+/// Gets how a key name is shown to the player
+///
+/// # Arguments
+/// * `key` - The key name, as written in the config
+///
+/// # Returns
+/// The label, with arrows for the arrow keys and capitalised names for the others
+fn key_label(key: &str) -> String {
+    if let Some((modifier, base)) = key.split_once('+') {
+        if MODIFIERS.contains(&modifier) {
+            return format!("{}+{}", key_label(modifier), key_label(base));
+        }
+    }
+    match key {
+        "up" => "↑".to_string(),
+        "down" => "↓".to_string(),
+        "left" => "←".to_string(),
+        "right" => "→".to_string(),
+        "pageup" => "PgUp".to_string(),
+        "pagedown" => "PgDn".to_string(),
+        _ => {
+            // Named keys are capitalised, single characters are shown as they are typed
+            let mut chars = key.chars();
+            match chars.next() {
+                Some(first) if key.chars().count() > 1 => {
+                    first.to_uppercase().chain(chars).collect()
+                }
+                _ => key.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Splits a chord as it is written in the tests into the key names pressed
+    fn keys(chord: &str) -> Vec<String> {
+        chord.split_whitespace().map(str::to_string).collect()
+    }
+
+    /// Builds the default keymap with the given bindings on top
+    fn keymap(overrides: &[(&str, Option<Action>)]) -> Result<Keymap> {
+        let overrides: BTreeMap<String, Option<Action>> = overrides
+            .iter()
+            .map(|(chord, action)| (chord.to_string(), *action))
+            .collect();
+        Keymap::with_overrides(&overrides)
+    }
+
+    #[test]
+    fn chords_are_split_into_known_keys() {
+        assert_eq!(parse_chord("g g").unwrap(), keys("g g"));
+        assert_eq!(parse_chord("ctrl+d").unwrap(), keys("ctrl+d"));
+        assert_eq!(parse_chord("alt+pageup  x").unwrap(), keys("alt+pageup x"));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(parse_chord("").is_err());
+        assert!(parse_chord("shift+g").is_err());
+        assert!(parse_chord("g spacebar").is_err());
+        assert!(keymap(&[("f13", Some(Action::Hire))]).is_err());
+    }
+
+    #[test]
+    fn chords_wait_for_their_last_key() {
+        let keymap: Keymap = Keymap::default();
+
+        assert!(keymap.resolve(&keys("g")) == KeyMatch::Pending);
+        assert!(keymap.resolve(&keys("g g")) == KeyMatch::Action(Action::SelectFirst));
+        assert!(keymap.resolve(&keys("g j")) == KeyMatch::Unbound);
+        assert!(keymap.resolve(&keys("j")) == KeyMatch::Action(Action::SelectNext));
+        assert!(keymap.resolve(&keys("x")) == KeyMatch::Unbound);
+    }
+
+    #[test]
+    fn overrides_rebind_and_unbind_keys() {
+        let keymap: Keymap = keymap(&[
+            ("x", Some(Action::Hire)),
+            ("n", None),
+            ("z z", Some(Action::Quit)),
+        ])
+        .unwrap();
+
+        assert!(keymap.resolve(&keys("x")) == KeyMatch::Action(Action::Hire));
+        assert!(keymap.resolve(&keys("n")) == KeyMatch::Unbound);
+        assert!(keymap.resolve(&keys("z")) == KeyMatch::Pending);
+        assert!(keymap.resolve(&keys("z z")) == KeyMatch::Action(Action::Quit));
+        assert_eq!(keymap.label(Action::Hire).as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn keys_starting_a_bound_chord_are_rejected() {
+        assert!(keymap(&[("g", Some(Action::Hire))]).is_err());
+        assert!(keymap(&[("q q", Some(Action::Quit))]).is_err());
+
+        // Unbinding the chord frees its first key
+        let keymap: Keymap = keymap(&[("g", Some(Action::Hire)), ("g g", None)]).unwrap();
+        assert!(keymap.resolve(&keys("g")) == KeyMatch::Action(Action::Hire));
+    }
+}
@@ -0,0 +1,481 @@
+use crate::game::FRAME_DURATION_MS;
+use crate::race::{Meet, TEAM_CLUB};
+use crate::rng::GameRng;
+use crate::utils::NameData;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Number of divisions in the league, division 1 is the top
+pub const DIVISIONS: usize = 4;
+
+/// Clubs in every division, the team's club included
+const CLUBS_PER_DIVISION: usize = 6;
+
+/// Weeks in a league season, one meet is raced every week
+pub const WEEKS_PER_SEASON: u32 = 8;
+
+/// Ticks in a league week (~1 minute)
+pub const WEEK_TICKS: u64 = 60_000_u64 / FRAME_DURATION_MS;
+
+/// Clubs moving between divisions at the end of every season, up and down each
+pub const PROMOTION_PLACES: usize = 2;
+
+/// Most weekly meets raced for the time the game was closed
+pub const MAX_OFFLINE_WEEKS: u64 = 2_u64 * WEEKS_PER_SEASON as u64;
+
+/// League points earned for every finishing place at a weekly meet
+const POINTS_BY_PLACE: [u32; CLUBS_PER_DIVISION] = [10, 7, 5, 3, 2, 1];
+
+/// Prize money for winning a weekly meet in the bottom division, doubling with every division up
+const BASE_PRIZE_POOL: u64 = 40_u64;
+
+/// Speed of the swimmers bottom division clubs send, a little above a new hire's
+const BOTTOM_DIVISION_SPEED: f64 = 0.8_f64;
+
+/// How much faster the swimmers of every division up are
+const DIVISION_SPEED_STEP: f64 = 1.6_f64;
+
+/// Names tried for a new club before an already taken one is told apart by a number
+const NAME_ATTEMPTS: u32 = 20_u32;
+
+/// Distances weekly meets are swum over, in lengths
+const DISTANCES: [u64; 3] = [2_u64, 4_u64, 8_u64];
+
+/// Endings of the AI clubs' names
+const CLUB_KINDS: [&str; 5] = ["Swim Club", "Aquatics", "Dolphins", "Sharks", "Marlins"];
+
+/// Kinds of weekly meet, used to name them
+const FIXTURE_KINDS: [&str; 4] = ["Gala", "Trophy", "Cup", "Derby"];
+
+/// A club in the league, either an AI club or the team's own
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Club {
+    pub name: String, // Name shown in the standings
+    #[serde(default)]
+    pub speed: f64, // Speed of the swimmer the club sends to meets, unused for the team
+    pub is_player: bool, // Whether this is the team's own club
+    pub points: u32,  // League points this season
+    pub wins: u32,    // Weekly meets won this season
+    pub played: u32,  // Weekly meets raced this season
+}
+
+impl Club {
+    /// Creates an AI club with a random name and a speed suited to its division
+    ///
+    /// # Arguments
+    /// * `name_data` - Name data, clubs are named after famous swimmers
+    /// * `division` - Index of the division, 0 is the top
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// A new club with no points
+    fn generate(name_data: &NameData, division: usize, rng: &mut GameRng) -> Self {
+        let host: &str = name_data
+            .last_names
+            .choose(rng)
+            .map_or("County", String::as_str);
+        let kind: &str = CLUB_KINDS.choose(rng).copied().unwrap_or("Swim Club");
+
+        Self {
+            name: format!("{} {}", host, kind),
+            speed: division_speed(division, rng),
+            is_player: false,
+            points: 0_u32,
+            wins: 0_u32,
+            played: 0_u32,
+        }
+    }
+
+    /// Records the result of a weekly meet
+    ///
+    /// # Arguments
+    /// * `place` - Finishing place, starting at 1
+    fn record(&mut self, place: usize) {
+        self.points += POINTS_BY_PLACE
+            .get(place.saturating_sub(1_usize))
+            .copied()
+            .unwrap_or(0_u32);
+        self.played += 1_u32;
+        if place == 1_usize {
+            self.wins += 1_u32;
+        }
+    }
+}
+
+/// How the team's club ended a league season
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SeasonOutcome {
+    Promoted,  // Moved up a division
+    Relegated, // Moved down a division
+    Stayed,    // Stays in the same division
+}
+
+/// The league of AI clubs the team's club plays in, with its divisions and season calendar
+#[derive(Default, Serialize, Deserialize)]
+pub struct League {
+    divisions: Vec<Vec<Club>>, // Clubs of every division, top division first
+    fixtures: Vec<Meet>,       // The season's weekly meets, in calendar order
+    pub week: u32,             // Weekly meets raced this season
+    pub seasons: u32,          // League seasons completed
+    ticks_since_week: u64,     // Ticks since the last weekly meet
+}
+
+impl League {
+    /// Creates a league with the team's club at the bottom of the lowest division
+    ///
+    /// # Arguments
+    /// * `name_data` - Name data for the AI clubs
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// A new league at the start of its first season
+    pub fn new(name_data: &NameData, rng: &mut GameRng) -> Self {
+        // Results are matched up by club name, so every name must be unique
+        let mut divisions: Vec<Vec<Club>> = vec![Vec::new(); DIVISIONS];
+        let mut names: Vec<String> = vec![TEAM_CLUB.to_string()];
+        for (division, clubs) in divisions.iter_mut().enumerate() {
+            while clubs.len() < CLUBS_PER_DIVISION {
+                let mut club: Club = Club::generate(name_data, division, rng);
+                let mut attempts: u32 = 1_u32;
+                while names.contains(&club.name) && attempts < NAME_ATTEMPTS {
+                    club = Club::generate(name_data, division, rng);
+                    attempts += 1_u32;
+                }
+
+                // With few names to pick from, a taken one gets a number, as in "Ledecky Sharks 2"
+                club.name = numbered_name(&club.name, &names);
+                names.push(club.name.clone());
+                clubs.push(club);
+            }
+        }
+
+        // The team's club takes the place of one of the bottom division's clubs
+        if let Some(bottom) = divisions.last_mut() {
+            bottom.pop();
+            bottom.push(Club {
+                name: TEAM_CLUB.to_string(),
+                speed: 0_f64,
+                is_player: true,
+                points: 0_u32,
+                wins: 0_u32,
+                played: 0_u32,
+            });
+        }
+
+        let mut league: League = Self {
+            divisions,
+            fixtures: Vec::new(),
+            week: 0_u32,
+            seasons: 0_u32,
+            ticks_since_week: 0_u64,
+        };
+        league.schedule_fixtures(name_data, rng);
+        league
+    }
+
+    /// Gets whether the league has been set up, older saves have none
+    pub fn is_empty(&self) -> bool {
+        self.divisions.is_empty()
+    }
+
+    /// Gives a speed to the AI clubs of older saves, which only had one relative to the team
+    ///
+    /// # Arguments
+    /// * `rng` - The game's random number generator
+    pub fn rate_clubs(&mut self, rng: &mut GameRng) {
+        for (division, clubs) in self.divisions.iter_mut().enumerate() {
+            for club in clubs.iter_mut() {
+                if !club.is_player && club.speed <= 0_f64 {
+                    club.speed = division_speed(division, rng);
+                }
+            }
+        }
+    }
+
+    /// Draws up the calendar of weekly meets for the coming season
+    ///
+    /// # Arguments
+    /// * `name_data` - Name data, meets are named after famous swimmers
+    /// * `rng` - The game's random number generator
+    fn schedule_fixtures(&mut self, name_data: &NameData, rng: &mut GameRng) {
+        let prize: u64 = self.prize_pool();
+        self.fixtures = (0_u32..WEEKS_PER_SEASON)
+            .map(|_| {
+                let host: &str = name_data
+                    .last_names
+                    .choose(rng)
+                    .map_or("County", String::as_str);
+                let kind: &str = FIXTURE_KINDS.choose(rng).copied().unwrap_or("Gala");
+                Meet {
+                    name: format!("{} {}", host, kind),
+                    distance: DISTANCES.choose(rng).copied().unwrap_or(4_u64),
+                    prize,
+                }
+            })
+            .collect();
+    }
+
+    /// Gets the index of the division the team's club plays in, 0 is the top
+    pub fn player_division(&self) -> usize {
+        self.divisions
+            .iter()
+            .position(|clubs: &Vec<Club>| clubs.iter().any(|club: &Club| club.is_player))
+            .unwrap_or(DIVISIONS - 1_usize)
+    }
+
+    /// Gets the prize money for winning a weekly meet in the team's division
+    pub fn prize_pool(&self) -> u64 {
+        let steps_up: usize = DIVISIONS - 1_usize - self.player_division();
+        BASE_PRIZE_POOL * 2_u64.pow(steps_up as u32)
+    }
+
+    /// Gets the clubs of the team's division, best first
+    pub fn standings(&self) -> Vec<&Club> {
+        let mut clubs: Vec<&Club> = self
+            .divisions
+            .get(self.player_division())
+            .map(|clubs: &Vec<Club>| clubs.iter().collect())
+            .unwrap_or_default();
+        clubs.sort_by(|a: &&Club, b: &&Club| b.points.cmp(&a.points).then(b.wins.cmp(&a.wins)));
+        clubs
+    }
+
+    /// Gets the weekly meets still to be raced this season, the next one first
+    pub fn upcoming_fixtures(&self) -> &[Meet] {
+        self.fixtures.get(self.week as usize..).unwrap_or(&[])
+    }
+
+    /// Gets the AI clubs of the team's division, which send a swimmer to every weekly meet
+    ///
+    /// # Returns
+    /// The name and swimmer speed of every club but the team's
+    pub fn rivals(&self) -> Vec<(String, f64)> {
+        self.divisions
+            .get(self.player_division())
+            .map(|clubs: &Vec<Club>| {
+                clubs
+                    .iter()
+                    .filter(|club: &&Club| !club.is_player)
+                    .map(|club: &Club| (club.name.clone(), club.speed))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Counts down to the next weekly meet
+    ///
+    /// # Arguments
+    /// * `ticks` - Ticks that have passed
+    ///
+    /// # Returns
+    /// The number of weekly meets that are now due
+    pub fn advance(&mut self, ticks: u64) -> u64 {
+        self.ticks_since_week += ticks;
+        let weeks: u64 = self.ticks_since_week / WEEK_TICKS;
+        self.ticks_since_week %= WEEK_TICKS;
+        weeks
+    }
+
+    /// Gets the ticks left until the next weekly meet
+    pub fn ticks_to_next_week(&self) -> u64 {
+        WEEK_TICKS - self.ticks_since_week
+    }
+
+    /// Gets the weekly meet due to be raced next
+    pub fn next_fixture(&self) -> Option<&Meet> {
+        self.fixtures.get(self.week as usize)
+    }
+
+    /// Records the results of the week's meet in every division
+    ///
+    /// The team's division passes in the order its clubs finished the heat.
+    /// The other divisions have no one watching, so their meets are settled
+    /// by the speed of each club's swimmer with a little luck.
+    ///
+    /// # Arguments
+    /// * `player_results` - Names of the clubs of the team's division, winner first
+    /// * `rng` - The game's random number generator
+    pub fn record_week(&mut self, player_results: &[String], rng: &mut GameRng) {
+        let player_division: usize = self.player_division();
+
+        for (division, clubs) in self.divisions.iter_mut().enumerate() {
+            let order: Vec<String> = if division == player_division {
+                player_results.to_vec()
+            } else {
+                let mut scored: Vec<(f64, String)> = clubs
+                    .iter()
+                    .map(|club: &Club| {
+                        (
+                            club.speed * rng.random_range(0.9_f64..1.1_f64),
+                            club.name.clone(),
+                        )
+                    })
+                    .collect();
+                scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+                scored.into_iter().map(|(_, name)| name).collect()
+            };
+
+            for club in clubs.iter_mut() {
+                let place: usize = order
+                    .iter()
+                    .position(|name: &String| *name == club.name)
+                    .map_or(CLUBS_PER_DIVISION, |index: usize| index + 1_usize);
+                club.record(place);
+            }
+        }
+        self.week += 1_u32;
+    }
+
+    /// Gets whether every weekly meet of the season has been raced
+    pub fn is_season_over(&self) -> bool {
+        self.week >= WEEKS_PER_SEASON
+    }
+
+    /// Ends the season: the top clubs of every division go up, the bottom ones go down
+    ///
+    /// # Arguments
+    /// * `name_data` - Name data for the next season's fixtures
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// Whether the team's club was promoted, relegated or stayed
+    pub fn end_season(&mut self, name_data: &NameData, rng: &mut GameRng) -> SeasonOutcome {
+        let before: usize = self.player_division();
+
+        // The top clubs of every division but the first go up, the bottom ones of every
+        // division but the last go down, all decided on the final tables
+        let mut divisions: Vec<Vec<Club>> = vec![Vec::new(); DIVISIONS];
+        for (division, mut clubs) in std::mem::take(&mut self.divisions).into_iter().enumerate() {
+            clubs.sort_by(|a: &Club, b: &Club| b.points.cmp(&a.points).then(b.wins.cmp(&a.wins)));
+            for (i, mut club) in clubs.into_iter().enumerate() {
+                let target: usize = if i < PROMOTION_PLACES && division > 0_usize {
+                    division - 1_usize
+                } else if i >= CLUBS_PER_DIVISION - PROMOTION_PLACES
+                    && division < DIVISIONS - 1_usize
+                {
+                    division + 1_usize
+                } else {
+                    division
+                };
+                // AI clubs that change division send swimmers of their new division's speed
+                if target != division && !club.is_player {
+                    club.speed = division_speed(target, rng);
+                }
+                divisions[target].push(club);
+            }
+        }
+        self.divisions = divisions;
+
+        for club in self.divisions.iter_mut().flatten() {
+            club.points = 0_u32;
+            club.wins = 0_u32;
+            club.played = 0_u32;
+        }
+        self.week = 0_u32;
+        self.seasons += 1_u32;
+        self.schedule_fixtures(name_data, rng);
+
+        let after: usize = self.player_division();
+        if after < before {
+            SeasonOutcome::Promoted
+        } else if after > before {
+            SeasonOutcome::Relegated
+        } else {
+            SeasonOutcome::Stayed
+        }
+    }
+}
+
+/// Rolls the speed of the swimmer a club of a division sends to meets
+///
+/// # Arguments
+/// * `division` - Index of the division, 0 is the top
+/// * `rng` - The game's random number generator
+///
+/// # Returns
+/// The division's speed, give or take a tenth
+fn division_speed(division: usize, rng: &mut GameRng) -> f64 {
+    let steps_up: i32 = (DIVISIONS - 1_usize - division) as i32;
+    BOTTOM_DIVISION_SPEED * DIVISION_SPEED_STEP.powi(steps_up) * rng.random_range(0.9_f64..1.1_f64)
+}
+
+/// Tells a club name apart from the taken ones with a number
+///
+/// # Arguments
+/// * `name` - The name the club was generated with
+/// * `taken` - Names of the clubs already in the league
+///
+/// # Returns
+/// The name itself if it is free, otherwise the first free one of "name 2", "name 3", ...
+fn numbered_name(name: &str, taken: &[String]) -> String {
+    let mut candidate: String = name.to_string();
+    let mut number: usize = 1_usize;
+    while taken.contains(&candidate) {
+        number += 1_usize;
+        candidate = format!("{} {}", name, number);
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clubs_get_unique_names_without_name_data() {
+        // With no names to pick from every club is generated as "County" something
+        let league: League = League::new(&NameData::default(), &mut GameRng::seeded(1_u64));
+
+        let names: Vec<&String> = league
+            .divisions
+            .iter()
+            .flatten()
+            .map(|club: &Club| &club.name)
+            .collect();
+        assert_eq!(names.len(), DIVISIONS * CLUBS_PER_DIVISION);
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[..i].contains(name), "{} is taken twice", name);
+        }
+    }
+
+    #[test]
+    fn taken_names_are_told_apart_by_numbers() {
+        let taken: Vec<String> = vec!["County Sharks".to_string(), "County Sharks 2".to_string()];
+
+        assert_eq!(numbered_name("Phelps Sharks", &taken), "Phelps Sharks");
+        assert_eq!(numbered_name("County Sharks", &taken), "County Sharks 3");
+    }
+
+    #[test]
+    fn higher_divisions_send_faster_swimmers() {
+        let mut rng: GameRng = GameRng::seeded(7_u64);
+
+        let speeds: Vec<f64> = (0_usize..DIVISIONS)
+            .map(|division: usize| division_speed(division, &mut rng))
+            .collect();
+        assert!(speeds.windows(2).all(|pair: &[f64]| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn old_clubs_are_rated_once() {
+        let mut rng: GameRng = GameRng::seeded(3_u64);
+        let mut league: League = League::new(&NameData::default(), &mut rng);
+        for club in league.divisions.iter_mut().flatten() {
+            club.speed = 0_f64;
+        }
+
+        league.rate_clubs(&mut rng);
+        let speeds: Vec<f64> = league
+            .divisions
+            .iter()
+            .flatten()
+            .map(|club: &Club| club.speed)
+            .collect();
+        league.rate_clubs(&mut rng);
+
+        for (club, speed) in league.divisions.iter().flatten().zip(speeds) {
+            assert_eq!(club.speed, speed);
+            assert_eq!(club.speed > 0_f64, !club.is_player);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! The swimming idle game model, free of any terminal code
+//!
+//! A [`Game`] only changes when it is told to: [`Game::tick`] advances the
+//! simulation by a span of time and [`Game::apply`] carries out an [`Action`]
+//! from the player, so the same game can be driven by the terminal UI, a
+//! tool or a test.
+
+pub mod achievements;
+pub mod action;
+pub mod config;
+pub mod events;
+pub mod game;
+pub mod keymap;
+pub mod league;
+pub mod modifiers;
+pub mod notifications;
+pub mod perks;
+pub mod pool;
+pub mod prestige;
+pub mod race;
+pub mod relay;
+pub mod rng;
+pub mod save;
+pub mod shop;
+pub mod stroke;
+pub mod swimmer;
+pub mod utils;
+pub mod wallet;
+
+pub use action::Action;
+pub use game::{Game, Screen};
+pub use swimmer::Swimmer;
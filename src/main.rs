@@ -1,17 +1,50 @@
-mod game;
-mod swimmer;
 mod ui;
-mod utils;
 
-use game::Game;
 use miette::Result;
+use swimming_idle::Game;
 
 /// The entry point of the application
 ///
 /// # Returns
 /// A Result indicating success or failure
 fn main() -> Result<()> {
-    let mut game: Game = Game::new()?;
-    game.run()?;
+    let seed: Option<u64> = parse_seed(std::env::args().skip(1))?;
+
+    // Make sure a panic never leaves the terminal in raw mode
+    ui::install_panic_hook();
+
+    let mut game: Game = Game::load(seed, ui::choose_backup)?;
+    ui::run(&mut game)?;
     Ok(())
 }
+
+/// Reads the seed for a new game from the command line, as `--seed 42` or `--seed=42`
+///
+/// # Arguments
+/// * `args` - The command line arguments, without the program name
+///
+/// # Returns
+/// A Result containing the seed if one was given, or an error for an argument that is not understood
+fn parse_seed(mut args: impl Iterator<Item = String>) -> Result<Option<u64>> {
+    let mut seed: Option<u64> = None;
+    while let Some(arg) = args.next() {
+        let value: String = match arg.strip_prefix("--seed=") {
+            Some(value) => value.to_string(),
+            None if arg == "--seed" => args
+                .next()
+                .ok_or_else(|| miette::miette!("--seed needs a number after it"))?,
+            None => {
+                return Err(miette::miette!(
+                    help = "Usage: swimming-idle-game-terminal [--seed <number>]",
+                    "Unknown argument '{}'",
+                    arg
+                ))
+            }
+        };
+        let parsed: u64 = value
+            .parse::<u64>()
+            .map_err(|_| miette::miette!("--seed needs a whole number, got '{}'", value))?;
+        seed = Some(parsed);
+    }
+    Ok(seed)
+}
@@ -0,0 +1,26 @@
+/// Global multipliers applied on top of every swimmer's own stats
+///
+/// Built fresh from the permanent upgrades and the pool whenever it is needed, so it is
+/// never saved and can never drift out of sync with what was bought.
+#[derive(Clone, Copy)]
+pub struct Modifiers {
+    pub speed_multiplier: f64,        // Multiplies every swimmer's speed
+    pub upgrade_cost_multiplier: f64, // Multiplies the price of swimmer upgrades
+    pub hire_speed_bonus: f64,        // Extra starting speed for newly hired swimmers
+    pub length_scale: u64,            // 25 m lengths in one length of the pool
+    pub wall_time_multiplier: f64,    // Multiplies the time lost starting and turning
+    pub energy_multiplier: f64,       // Multiplies the energy used up by every length
+}
+
+impl Default for Modifiers {
+    fn default() -> Self {
+        Self {
+            speed_multiplier: 1_f64,
+            upgrade_cost_multiplier: 1_f64,
+            hire_speed_bonus: 0_f64,
+            length_scale: 1_u64,
+            wall_time_multiplier: 1_f64,
+            energy_multiplier: 1_f64,
+        }
+    }
+}
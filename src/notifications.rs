@@ -0,0 +1,297 @@
+use crate::action::Action;
+use crate::events::{GameEvent, Subscriber};
+use crate::game::FRAME_DURATION_MS;
+use crate::keymap::Keymap;
+use crate::league::SeasonOutcome;
+use crate::race::{medal, ordinal};
+use crate::relay::RELAY_SIZE;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// How long a notification stays on screen
+const NOTIFICATION_LIFETIME: Duration = Duration::from_secs(3_u64);
+
+/// Maximum number of notifications shown at once, older ones are dropped first
+pub const MAX_NOTIFICATIONS: usize = 3_usize;
+
+/// Whether a notification reports something that worked, something that failed or an unlocked achievement
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Success,
+    Failure,
+    Achievement,
+}
+
+/// A short-lived message shown in the footer area
+pub struct Notification {
+    pub message: String,        // Text shown to the player
+    pub kind: NotificationKind, // Controls the colors of the message
+    remaining: Duration,        // Game time left before the notification disappears
+}
+
+/// Queue of the notifications currently on screen, newest last
+#[derive(Default)]
+pub struct Notifications {
+    queue: VecDeque<Notification>,
+    keymap: Keymap, // Keys named in messages that tell the player what to press
+}
+
+impl Notifications {
+    /// Creates an empty queue whose messages name the keys of a keymap
+    ///
+    /// # Arguments
+    /// * `keymap` - The key bindings in use
+    pub fn new(keymap: Keymap) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            keymap,
+        }
+    }
+
+    /// Adds a notification, dropping the oldest one if the stack is full
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the notification reports a success, a failure or an achievement
+    /// * `message` - Text shown to the player
+    pub fn push(&mut self, kind: NotificationKind, message: String) {
+        if self.queue.len() >= MAX_NOTIFICATIONS {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(Notification {
+            message,
+            kind,
+            remaining: NOTIFICATION_LIFETIME,
+        });
+    }
+
+    /// Adds a success notification
+    ///
+    /// # Arguments
+    /// * `message` - Text shown to the player
+    pub fn success(&mut self, message: String) {
+        self.push(NotificationKind::Success, message);
+    }
+
+    /// Adds a failure notification
+    ///
+    /// # Arguments
+    /// * `message` - Text shown to the player
+    pub fn failure(&mut self, message: String) {
+        self.push(NotificationKind::Failure, message);
+    }
+
+    /// Adds a notification for an achievement that was just unlocked
+    ///
+    /// # Arguments
+    /// * `message` - Text shown to the player
+    pub fn achievement(&mut self, message: String) {
+        self.push(NotificationKind::Achievement, message);
+    }
+
+    /// Ages every notification and removes those whose lifetime has run out
+    ///
+    /// # Arguments
+    /// * `elapsed` - Game time since the notifications were last aged
+    pub fn advance(&mut self, elapsed: Duration) {
+        for notification in &mut self.queue {
+            notification.remaining = notification.remaining.saturating_sub(elapsed);
+        }
+        self.queue
+            .retain(|notification: &Notification| !notification.remaining.is_zero());
+    }
+
+    /// Gets the notifications currently on screen, oldest first
+    ///
+    /// # Returns
+    /// An iterator over the active notifications
+    pub fn iter(&self) -> impl Iterator<Item = &Notification> {
+        self.queue.iter()
+    }
+}
+
+impl Subscriber for Notifications {
+    /// Tells the player about every event worth a message
+    ///
+    /// # Arguments
+    /// * `event` - The event that happened
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::LengthsCompleted { .. } => {}
+            GameEvent::LevelledUp { swimmer, level } => self.success(format!(
+                "⭐ {} reached level {}! Press {} to pick a perk.",
+                swimmer,
+                level,
+                self.keymap.key(Action::Confirm)
+            )),
+            GameEvent::Exhausted { swimmer } => self.failure(format!(
+                "❌ {} is exhausted! Press {} to let them rest.",
+                swimmer,
+                self.keymap.key(Action::Rest)
+            )),
+            GameEvent::PerkLearned { swimmer, perk } => {
+                self.success(format!("✅ {} learned {}!", swimmer, perk))
+            }
+            GameEvent::Upgraded {
+                swimmer,
+                stat,
+                value,
+            } => self.success(format!(
+                "✅ {} upgraded {} to {}!",
+                swimmer,
+                stat.name(),
+                value
+            )),
+            GameEvent::Retrained { swimmer, stroke } => {
+                self.success(format!("✅ {} now swims {}!", swimmer, stroke.name()))
+            }
+            GameEvent::SwimmerHired { swimmer, speed, .. } => self.success(format!(
+                "✅ New swimmer {} joined with speed {:.1}!",
+                swimmer, speed
+            )),
+            GameEvent::RestToggled { swimmer, resting } => self.success(if *resting {
+                format!("💤 {} is resting on the bench.", swimmer)
+            } else {
+                format!("✅ {} is back in the pool!", swimmer)
+            }),
+            GameEvent::LengthsConverted { points } => {
+                self.success(format!("✅ Converted lengths into {} points!", points))
+            }
+            GameEvent::Purchased { item, level } => {
+                self.success(format!("✅ {} upgraded to level {}!", item, level))
+            }
+            GameEvent::PurchaseFailed {
+                item,
+                currency,
+                needed,
+            } => self.failure(format!(
+                "❌ Not enough {}! Need {} more for {}.",
+                currency.name(),
+                needed,
+                item
+            )),
+            GameEvent::PoolLengthChanged { length } => self.success(format!(
+                "✅ The team now trains in the {} pool!",
+                length.name()
+            )),
+            GameEvent::SeasonStarted { season, trophies } => self.success(format!(
+                "✅ Season {} begins! Earned {} trophies.",
+                season, trophies
+            )),
+            GameEvent::LanesFull { lanes } => self.failure(format!(
+                "❌ All {} lanes are taken! Add a lane in the facilities to hire more.",
+                lanes
+            )),
+            GameEvent::MeetAnnounced { meet } => self.success(format!(
+                "🏁 The {} is open for entries! Press {} to enter.",
+                meet,
+                self.keymap.key(Action::Meet)
+            )),
+            GameEvent::MeetNotOpen { seconds } => self.failure(format!(
+                "❌ No meet open yet! The next one is announced in {}s.",
+                seconds
+            )),
+            GameEvent::MeetFull { entrants } => self.failure(format!(
+                "❌ Only {} swimmers can enter a meet! Withdraw one first.",
+                entrants
+            )),
+            GameEvent::NoEntrants => self.failure(format!(
+                "❌ Pick at least one swimmer with {} first!",
+                self.keymap.key(Action::Toggle)
+            )),
+            GameEvent::RaceFinished {
+                meet,
+                best_place,
+                prize_money,
+                ..
+            } => self.success(format!(
+                "{} Best finish at the {}: {}! Won {} prize money.",
+                if *best_place <= 3_usize {
+                    medal(*best_place)
+                } else {
+                    "🏁"
+                },
+                meet,
+                ordinal(*best_place),
+                prize_money
+            )),
+            GameEvent::LeagueWeekRaced {
+                week,
+                meet,
+                place,
+                prize_money,
+            } => self.success(format!(
+                "🏆 Week {}: {} at the {}, won {} prize money.",
+                week,
+                ordinal(*place),
+                meet,
+                prize_money
+            )),
+            GameEvent::LeagueSeasonEnded { outcome, division } => {
+                let division: usize = division + 1_usize;
+                self.success(match outcome {
+                    SeasonOutcome::Promoted => format!(
+                        "🏆 Promoted to division {}! Prizes are bigger up here.",
+                        division
+                    ),
+                    SeasonOutcome::Relegated => format!(
+                        "❌ Relegated to division {}. Prizes are smaller down here.",
+                        division
+                    ),
+                    SeasonOutcome::Stayed => {
+                        format!("🏆 League season over, staying in division {}.", division)
+                    }
+                })
+            }
+            GameEvent::RelayFormed { relay, leg_lengths } => self.success(format!(
+                "✅ {} formed! Each swimmer swims {} lengths in turn.",
+                relay, leg_lengths
+            )),
+            GameEvent::RelayDisbanded { relay } => {
+                self.success(format!("✅ {} disbanded, back to their own lanes!", relay))
+            }
+            GameEvent::RelayFinished {
+                relay,
+                ticks,
+                bonus_lengths,
+            } => self.success(format!(
+                "🔁 {} finished in {:.2}s! +{} bonus lengths.",
+                relay,
+                (ticks * FRAME_DURATION_MS) as f64 / 1000_f64,
+                bonus_lengths
+            )),
+            GameEvent::RelayMemberBusy {
+                swimmer,
+                relay,
+                resting,
+            } => self.failure(if *resting {
+                format!(
+                    "❌ {} swims in {}! Press {} to disband it first.",
+                    swimmer,
+                    relay,
+                    self.keymap.key(Action::Relay)
+                )
+            } else {
+                format!("❌ {} already swims in {}!", swimmer, relay)
+            }),
+            GameEvent::RelayShort { missing, picked } => self.failure(if *picked {
+                format!(
+                    "❌ Pick {} more swimmers with {} first!",
+                    missing,
+                    self.keymap.key(Action::Toggle)
+                )
+            } else {
+                format!(
+                    "❌ A relay needs {} swimmers who are not in one already! Hire {} more.",
+                    RELAY_SIZE, missing
+                )
+            }),
+            GameEvent::RelayFull { legs } => self.failure(format!(
+                "❌ A relay has {} legs! Drop a swimmer first.",
+                legs
+            )),
+            GameEvent::AchievementUnlocked { name, bonus } => {
+                self.achievement(format!("🏅 Achievement unlocked: {}! {}.", name, bonus))
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::rng::GameRng;
+use miette::{IntoDiagnostic, Result};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Number of perks offered to pick from on every level-up
+pub const PERK_CHOICES: usize = 3;
+
+/// What a perk does for the swimmer who picked it
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PerkEffect {
+    Speed { bonus: f64 },              // Faster on every length
+    ReturnSpeed { bonus: f64 },        // Faster on lengths swum back towards the left wall
+    OutwardSpeed { bonus: f64 },       // Faster on lengths swum away from the left wall
+    UpgradeDiscount { discount: f64 }, // Cheaper stat upgrades and retraining
+    BonusLength { every: u64 },        // An extra length credited every so many turns
+    EnergySaver { reduction: f64 },    // Less energy used per length
+    XpBoost { bonus: f64 },            // More XP per length
+}
+
+/// A trait a swimmer can pick when they level up
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Perk {
+    pub id: String,          // Stable identifier, used to avoid offering a perk twice
+    pub name: String,        // Name shown in the UI
+    pub description: String, // What the perk does, shown in the UI
+    pub effect: PerkEffect,
+}
+
+/// Every perk that can be rolled, loaded from `src/data/perks.json`
+#[derive(Default, Deserialize)]
+pub struct PerkData {
+    pub perks: Vec<Perk>,
+}
+
+impl PerkData {
+    /// Rolls a random set of perks to offer a swimmer who levelled up
+    ///
+    /// Perks the swimmer already has are only offered once every other perk is taken.
+    ///
+    /// # Arguments
+    /// * `owned` - Perks the swimmer already has
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// Up to `PERK_CHOICES` different perks
+    pub fn roll(&self, owned: &[Perk], rng: &mut GameRng) -> Vec<Perk> {
+        let fresh: Vec<&Perk> = self
+            .perks
+            .iter()
+            .filter(|perk: &&Perk| !owned.iter().any(|owned_perk| owned_perk.id == perk.id))
+            .collect();
+        let pool: Vec<&Perk> = if fresh.is_empty() {
+            self.perks.iter().collect()
+        } else {
+            fresh
+        };
+
+        pool.choose_multiple(rng, PERK_CHOICES)
+            .map(|perk: &&Perk| (*perk).clone())
+            .collect()
+    }
+}
+
+/// The perk catalogue, built into the game so it runs from any directory
+const PERK_DATA: &str = include_str!("data/perks.json");
+
+/// Loads the perk catalogue from the JSON built into the game
+///
+/// # Returns
+/// A Result containing the PerkData or an error
+pub fn load_perk_data() -> Result<PerkData> {
+    let perk_data: PerkData = serde_json::from_str::<PerkData>(PERK_DATA).into_diagnostic()?;
+    Ok(perk_data)
+}
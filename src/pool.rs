@@ -0,0 +1,198 @@
+use crate::modifiers::Modifiers;
+use crate::wallet::Wallet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lanes the pool has before any are added
+pub const BASE_LANES: usize = 6;
+
+/// Prize money it costs to build the 50 m pool, switching back and forth is free after that
+pub const LONG_COURSE_PRICE: u64 = 200_u64;
+
+/// How long the pool is, which decides how far a length is
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoolLength {
+    /// 25 m, the pool every club starts with
+    #[default]
+    Short,
+    /// 50 m, twice as far between turns
+    Long,
+}
+
+impl PoolLength {
+    /// Gets the display name of the pool length
+    pub fn name(self) -> &'static str {
+        match self {
+            PoolLength::Short => "25 m",
+            PoolLength::Long => "50 m",
+        }
+    }
+
+    /// Gets how many 25 m lengths one length of the pool is
+    pub fn scale(self) -> u64 {
+        match self {
+            PoolLength::Short => 1_u64,
+            PoolLength::Long => 2_u64,
+        }
+    }
+
+    /// Gets the other pool length
+    pub fn other(self) -> Self {
+        match self {
+            PoolLength::Short => PoolLength::Long,
+            PoolLength::Long => PoolLength::Short,
+        }
+    }
+}
+
+/// Facility upgrades bought with prize money, each helping every swimmer in the pool
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Facility {
+    ExtraLane,
+    LaneRopes,
+    StartingBlocks,
+    HeatedWater,
+}
+
+impl Facility {
+    /// Every facility in the order it is listed on the facilities screen
+    pub const ALL: [Facility; 4] = [
+        Facility::ExtraLane,
+        Facility::LaneRopes,
+        Facility::StartingBlocks,
+        Facility::HeatedWater,
+    ];
+
+    /// Gets the display name of the facility
+    pub fn name(self) -> &'static str {
+        match self {
+            Facility::ExtraLane => "Extra Lane",
+            Facility::LaneRopes => "Lane Ropes",
+            Facility::StartingBlocks => "Starting Blocks",
+            Facility::HeatedWater => "Heated Water",
+        }
+    }
+
+    /// Gets a short description of what one level of the facility does
+    pub fn description(self) -> &'static str {
+        match self {
+            Facility::ExtraLane => "+1 lane, room for one more swimmer",
+            Facility::LaneRopes => "+5% speed for every swimmer",
+            Facility::StartingBlocks => "-10% on start and turn times",
+            Facility::HeatedWater => "-10% energy used per length",
+        }
+    }
+
+    /// Gets the price of the first level, in prize money
+    fn base_price(self) -> f64 {
+        match self {
+            Facility::ExtraLane => 50_f64,
+            Facility::LaneRopes => 75_f64,
+            Facility::StartingBlocks => 60_f64,
+            Facility::HeatedWater => 60_f64,
+        }
+    }
+
+    /// Gets how much the price grows with each level bought
+    fn price_growth(self) -> f64 {
+        match self {
+            Facility::ExtraLane => 1.5_f64,
+            Facility::LaneRopes => 1.6_f64,
+            Facility::StartingBlocks => 1.6_f64,
+            Facility::HeatedWater => 1.7_f64,
+        }
+    }
+}
+
+/// The team's home pool: how many lanes it has, how long it is and what it is fitted with
+#[derive(Default, Serialize, Deserialize)]
+pub struct Pool {
+    pub length: PoolLength, // Length of the pool the team trains in
+    pub long_course: bool,  // Whether the 50 m pool has been built
+    levels: BTreeMap<Facility, u32>,
+}
+
+impl Pool {
+    /// Gets how many swimmers the pool has room for
+    pub fn lanes(&self) -> usize {
+        BASE_LANES + self.level(Facility::ExtraLane) as usize
+    }
+
+    /// Gets how many levels of a facility have been bought
+    ///
+    /// # Arguments
+    /// * `facility` - The facility
+    ///
+    /// # Returns
+    /// The current level, 0 if never bought
+    pub fn level(&self, facility: Facility) -> u32 {
+        self.levels.get(&facility).copied().unwrap_or(0_u32)
+    }
+
+    /// Gets the price of the next level of a facility
+    ///
+    /// # Arguments
+    /// * `facility` - The facility
+    ///
+    /// # Returns
+    /// The price in prize money
+    pub fn price(&self, facility: Facility) -> u64 {
+        (facility.base_price() * facility.price_growth().powi(self.level(facility) as i32)).ceil()
+            as u64
+    }
+
+    /// Buys the next level of a facility with prize money from the wallet
+    ///
+    /// # Arguments
+    /// * `facility` - The facility to buy
+    /// * `wallet` - The team wallet to pay from
+    ///
+    /// # Returns
+    /// `true` if successful, `false` if not enough prize money
+    pub fn buy(&mut self, facility: Facility, wallet: &mut Wallet) -> bool {
+        if !wallet.spend_prize_money(self.price(facility)) {
+            return false;
+        }
+        *self.levels.entry(facility).or_insert(0_u32) += 1_u32;
+        true
+    }
+
+    /// Gets the price of switching to the other pool length
+    ///
+    /// # Returns
+    /// The price in prize money, 0 once the 50 m pool has been built
+    pub fn switch_price(&self) -> u64 {
+        if self.long_course {
+            0_u64
+        } else {
+            LONG_COURSE_PRICE
+        }
+    }
+
+    /// Switches the team to the other pool length, building the 50 m pool the first time
+    ///
+    /// # Arguments
+    /// * `wallet` - The team wallet to pay from
+    ///
+    /// # Returns
+    /// `true` if successful, `false` if not enough prize money
+    pub fn switch_length(&mut self, wallet: &mut Wallet) -> bool {
+        if !wallet.spend_prize_money(self.switch_price()) {
+            return false;
+        }
+        self.long_course = true;
+        self.length = self.length.other();
+        true
+    }
+
+    /// Applies the pool length and the effects of every facility bought to the modifiers
+    ///
+    /// # Arguments
+    /// * `modifiers` - The modifiers to update
+    pub fn apply(&self, modifiers: &mut Modifiers) {
+        modifiers.length_scale = self.length.scale();
+        modifiers.speed_multiplier *= 1_f64 + 0.05_f64 * self.level(Facility::LaneRopes) as f64;
+        modifiers.wall_time_multiplier *= 0.9_f64.powi(self.level(Facility::StartingBlocks) as i32);
+        modifiers.energy_multiplier *= 0.9_f64.powi(self.level(Facility::HeatedWater) as i32);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::modifiers::Modifiers;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lifetime lengths a season needs for its first trophy, the requirement grows quadratically
+const LENGTHS_PER_TROPHY: f64 = 500_f64;
+
+/// Permanent bonuses bought with trophies, kept across every new season
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PrestigeItem {
+    HeadCoach,
+    TrainingCentre,
+    SwimAcademy,
+}
+
+impl PrestigeItem {
+    /// Every item in the order it is listed on the season screen
+    pub const ALL: [PrestigeItem; 3] = [
+        PrestigeItem::HeadCoach,
+        PrestigeItem::TrainingCentre,
+        PrestigeItem::SwimAcademy,
+    ];
+
+    /// Gets the display name of the item
+    pub fn name(self) -> &'static str {
+        match self {
+            PrestigeItem::HeadCoach => "Head Coach",
+            PrestigeItem::TrainingCentre => "Training Centre",
+            PrestigeItem::SwimAcademy => "Swim Academy",
+        }
+    }
+
+    /// Gets a short description of what one level of the item does
+    pub fn description(self) -> &'static str {
+        match self {
+            PrestigeItem::HeadCoach => "x1.25 speed for every swimmer",
+            PrestigeItem::TrainingCentre => "-10% on every swimmer upgrade",
+            PrestigeItem::SwimAcademy => "+0.5 starting speed for new hires",
+        }
+    }
+
+    /// Gets the price of the next level given how many levels are owned, in trophies
+    fn price(self, level: u32) -> u64 {
+        match self {
+            PrestigeItem::HeadCoach => 1_u64 + level as u64,
+            PrestigeItem::TrainingCentre => 2_u64 + 2_u64 * level as u64,
+            PrestigeItem::SwimAcademy => 1_u64 + 2_u64 * level as u64,
+        }
+    }
+}
+
+/// The prestige layer: trophies earned by retiring the club and the bonuses they bought
+#[derive(Default, Serialize, Deserialize)]
+pub struct Prestige {
+    pub trophies: u64, // Trophies available to spend
+    pub seasons: u32,  // Number of seasons completed
+    levels: BTreeMap<PrestigeItem, u32>,
+}
+
+impl Prestige {
+    /// Works out how many trophies retiring the club would earn
+    ///
+    /// # Arguments
+    /// * `season_lengths` - Lifetime lengths of every swimmer in the current season
+    ///
+    /// # Returns
+    /// The number of trophies, growing with the square root of the lengths
+    pub fn trophies_for(season_lengths: u64) -> u64 {
+        (season_lengths as f64 / LENGTHS_PER_TROPHY).sqrt().floor() as u64
+    }
+
+    /// Works out the lifetime lengths a season needs to earn the given number of trophies
+    ///
+    /// # Arguments
+    /// * `trophies` - The number of trophies
+    ///
+    /// # Returns
+    /// The lifetime lengths required
+    pub fn lengths_for(trophies: u64) -> u64 {
+        (trophies as f64 * trophies as f64 * LENGTHS_PER_TROPHY).ceil() as u64
+    }
+
+    /// Gets how many levels of an item have been bought
+    ///
+    /// # Arguments
+    /// * `item` - The prestige item
+    ///
+    /// # Returns
+    /// The current level, 0 if never bought
+    pub fn level(&self, item: PrestigeItem) -> u32 {
+        self.levels.get(&item).copied().unwrap_or(0_u32)
+    }
+
+    /// Gets the price of the next level of an item
+    ///
+    /// # Arguments
+    /// * `item` - The prestige item
+    ///
+    /// # Returns
+    /// The price in trophies
+    pub fn price(&self, item: PrestigeItem) -> u64 {
+        item.price(self.level(item))
+    }
+
+    /// Buys the next level of an item with trophies
+    ///
+    /// # Arguments
+    /// * `item` - The prestige item to buy
+    ///
+    /// # Returns
+    /// `true` if successful, `false` if not enough trophies
+    pub fn buy(&mut self, item: PrestigeItem) -> bool {
+        let price: u64 = self.price(item);
+        if self.trophies < price {
+            return false;
+        }
+        self.trophies -= price;
+        *self.levels.entry(item).or_insert(0_u32) += 1_u32;
+        true
+    }
+
+    /// Banks the trophies earned at the end of a season
+    ///
+    /// # Arguments
+    /// * `trophies` - Trophies earned by the season
+    pub fn complete_season(&mut self, trophies: u64) {
+        self.trophies += trophies;
+        self.seasons += 1_u32;
+    }
+
+    /// Applies the effects of every level bought to the modifiers
+    ///
+    /// # Arguments
+    /// * `modifiers` - The modifiers to update
+    pub fn apply(&self, modifiers: &mut Modifiers) {
+        modifiers.speed_multiplier *= 1.25_f64.powi(self.level(PrestigeItem::HeadCoach) as i32);
+        modifiers.upgrade_cost_multiplier *=
+            0.9_f64.powi(self.level(PrestigeItem::TrainingCentre) as i32);
+        modifiers.hire_speed_bonus += 0.5_f64 * self.level(PrestigeItem::SwimAcademy) as f64;
+    }
+}
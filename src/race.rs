@@ -0,0 +1,350 @@
+use crate::game::FRAME_DURATION_MS;
+use crate::modifiers::Modifiers;
+use crate::rng::GameRng;
+use crate::swimmer::{Swimmer, LANE_LENGTH};
+use crate::utils::{generate_random_name, NameData};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Ticks between a meet finishing and the next one being announced (~3 minutes)
+pub const MEET_INTERVAL_TICKS: u64 = 180_000_u64 / FRAME_DURATION_MS;
+
+/// Most swimmers the team can enter into a single meet
+pub const MAX_ENTRANTS: usize = 3;
+
+/// Lanes in a heat, rival swimmers fill the ones the team leaves empty
+const HEAT_LANES: usize = 6;
+
+/// Club shown next to the team's own swimmers
+pub const TEAM_CLUB: &str = "Your club";
+
+/// Club shown next to rivals who do not belong to a league club
+const RIVAL_CLUB: &str = "Rival";
+
+/// Distances a meet can be swum over, in lengths
+const DISTANCES: [u64; 3] = [2_u64, 4_u64, 8_u64];
+
+/// Kinds of meet, used to name them
+const MEET_KINDS: [&str; 4] = ["Invitational", "Open", "Classic", "Sprint Cup"];
+
+/// Prize money the winner earns for every length of the race, before the season bonus
+const PRIZE_PER_LENGTH: u64 = 25_u64;
+
+/// Medals won by finishing on the podium
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Medals {
+    pub gold: u32,   // Races won
+    pub silver: u32, // Races finished second
+    pub bronze: u32, // Races finished third
+}
+
+impl Medals {
+    /// Awards the medal for a finishing place, if it is on the podium
+    ///
+    /// # Arguments
+    /// * `place` - Finishing place, starting at 1
+    pub fn award(&mut self, place: usize) {
+        match place {
+            1 => self.gold += 1_u32,
+            2 => self.silver += 1_u32,
+            3 => self.bronze += 1_u32,
+            _ => {}
+        }
+    }
+
+    /// Gets the number of medals of every colour
+    pub fn total(&self) -> u32 {
+        self.gold + self.silver + self.bronze
+    }
+}
+
+/// Gets the medal shown for a finishing place
+///
+/// # Arguments
+/// * `place` - Finishing place, starting at 1
+///
+/// # Returns
+/// The medal emoji, or an empty string off the podium
+pub fn medal(place: usize) -> &'static str {
+    match place {
+        1 => "🥇",
+        2 => "🥈",
+        3 => "🥉",
+        _ => "",
+    }
+}
+
+/// Formats a finishing place as an ordinal (e.g. "2nd")
+///
+/// # Arguments
+/// * `place` - Finishing place, starting at 1
+///
+/// # Returns
+/// The place with its English suffix
+pub fn ordinal(place: usize) -> String {
+    let suffix: &str = match (place % 10, place % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", place, suffix)
+}
+
+/// A meet announced to the team, open for entries until it is raced
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Meet {
+    pub name: String,  // Name shown in the UI, e.g. "Phelps Invitational"
+    pub distance: u64, // Length of the race, in lengths
+    pub prize: u64,    // Prize money for the winner, lower places earn a share
+}
+
+impl Meet {
+    /// Announces a new meet with a random name and distance
+    ///
+    /// # Arguments
+    /// * `name_data` - Name data, the meet is named after a famous swimmer
+    /// * `seasons` - Seasons completed so far, later seasons pay bigger prizes
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// The new meet
+    pub fn announce(name_data: &NameData, seasons: u32, rng: &mut GameRng) -> Self {
+        let host: &str = name_data
+            .last_names
+            .choose(rng)
+            .map_or("County", String::as_str);
+        let kind: &str = MEET_KINDS.choose(rng).copied().unwrap_or("Open");
+        let distance: u64 = DISTANCES.choose(rng).copied().unwrap_or(4_u64);
+
+        Self {
+            name: format!("{} {}", host, kind),
+            distance,
+            prize: PRIZE_PER_LENGTH * distance * (1_u64 + seasons as u64),
+        }
+    }
+
+    /// Gets the prize money paid for a finishing place
+    ///
+    /// # Arguments
+    /// * `place` - Finishing place, starting at 1
+    ///
+    /// # Returns
+    /// The full prize for the winner, half for second, a quarter for third, nothing after
+    pub fn prize_for(&self, place: usize) -> u64 {
+        match place {
+            1 => self.prize,
+            2 => self.prize / 2_u64,
+            3 => self.prize / 4_u64,
+            _ => 0_u64,
+        }
+    }
+}
+
+/// A swimmer in one lane of a heat
+pub struct Racer {
+    pub swimmer: Swimmer,          // Copy made on the blocks, swum on its own
+    pub club: String,              // Club the swimmer races for
+    pub team_index: Option<usize>, // Index into the team's swimmers, `None` for a rival
+    pub finish: Option<f64>,       // Ticks taken to finish, once they touch the wall
+}
+
+/// A heat of a meet being swum, or already over and showing its results
+pub struct Race {
+    pub meet: Meet,     // The meet being raced
+    racers: Vec<Racer>, // Every swimmer in the heat, in lane order
+    elapsed: u64,       // Ticks since the start
+}
+
+impl Race {
+    /// Puts the team's entrants on the blocks next to rival swimmers
+    ///
+    /// Rivals are generated with speeds close to the entrants' average, so
+    /// stamina, turns, starts and energy decide most races.
+    ///
+    /// # Arguments
+    /// * `meet` - The meet being raced
+    /// * `entrants` - The team's entrants with their index into the team
+    /// * `name_data` - Name data for the rivals
+    /// * `modifiers` - Global modifiers the entrants swim with
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// A new race, waiting for its first tick
+    pub fn new(
+        meet: Meet,
+        entrants: &[(usize, &Swimmer)],
+        name_data: &NameData,
+        modifiers: &Modifiers,
+        rng: &mut GameRng,
+    ) -> Self {
+        // Rivals are matched to the entrants when fresh, so tired entrants are at a disadvantage
+        let average_speed: f64 = entrants
+            .iter()
+            .map(|(_, swimmer)| swimmer.effective_speed(modifiers) / swimmer.energy_factor())
+            .sum::<f64>()
+            / entrants.len().max(1_usize) as f64;
+
+        let rivals: Vec<(String, f64)> = (entrants.len()..HEAT_LANES.max(entrants.len() + 1_usize))
+            .map(|_| {
+                (
+                    RIVAL_CLUB.to_string(),
+                    average_speed * rng.random_range(0.85_f64..1.2_f64),
+                )
+            })
+            .collect();
+        Self::with_rivals(meet, entrants, &rivals, name_data, rng)
+    }
+
+    /// Puts the team's entrants on the blocks next to one rival from each of the given clubs
+    ///
+    /// # Arguments
+    /// * `meet` - The meet being raced
+    /// * `entrants` - The team's entrants with their index into the team
+    /// * `rivals` - Club of every rival and the speed they swim at
+    /// * `name_data` - Name data for the rivals
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// A new race, waiting for its first tick
+    pub fn with_rivals(
+        meet: Meet,
+        entrants: &[(usize, &Swimmer)],
+        rivals: &[(String, f64)],
+        name_data: &NameData,
+        rng: &mut GameRng,
+    ) -> Self {
+        let mut racers: Vec<Racer> = entrants
+            .iter()
+            .map(|(index, swimmer)| Racer {
+                swimmer: swimmer.on_blocks(),
+                club: TEAM_CLUB.to_string(),
+                team_index: Some(*index),
+                finish: None,
+            })
+            .collect();
+
+        for (club, speed) in rivals {
+            let name: String = generate_random_name(name_data, rng);
+            let mut rival: Swimmer = Swimmer::new(&name, 1_f64, rng);
+            // Rivals swim without modifiers, so their base speed is all there is
+            rival.speed = speed / rival.stroke.speed_multiplier();
+            racers.push(Racer {
+                swimmer: rival.on_blocks(),
+                club: club.clone(),
+                team_index: None,
+                finish: None,
+            });
+        }
+        racers.shuffle(rng);
+
+        Self {
+            meet,
+            racers,
+            elapsed: 0_u64,
+        }
+    }
+
+    /// Swims the whole heat at once, for meets raced without anyone watching
+    ///
+    /// # Arguments
+    /// * `modifiers` - Global modifiers the team's entrants swim with
+    pub fn run(&mut self, modifiers: &Modifiers) {
+        while !self.is_finished() {
+            self.tick(modifiers);
+        }
+    }
+
+    /// Advances every swimmer still racing by one tick
+    ///
+    /// # Arguments
+    /// * `modifiers` - Global modifiers the team's entrants swim with, rivals swim without
+    ///
+    /// # Returns
+    /// `true` on the tick the last swimmer finishes
+    pub fn tick(&mut self, modifiers: &Modifiers) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        // Meets are always swum in a 25 m pool, whatever the team trains in
+        let distance: f64 = self.distance();
+        let team_modifiers: Modifiers = Modifiers {
+            length_scale: 1_u64,
+            ..*modifiers
+        };
+        let rival_modifiers: Modifiers = Modifiers::default();
+        for racer in self
+            .racers
+            .iter_mut()
+            .filter(|racer: &&mut Racer| racer.finish.is_none())
+        {
+            let before: f64 = racer.swimmer.progress;
+            let racer_modifiers: &Modifiers = if racer.team_index.is_some() {
+                &team_modifiers
+            } else {
+                &rival_modifiers
+            };
+            racer.swimmer.swim(racer_modifiers);
+
+            // Work out when during the tick the wall was touched, for a fair photo finish
+            let after: f64 = racer.swimmer.progress;
+            if after >= distance {
+                let fraction: f64 = if after > before {
+                    (distance - before) / (after - before)
+                } else {
+                    1_f64
+                };
+                racer.finish = Some(self.elapsed as f64 + fraction);
+            }
+        }
+        self.elapsed += 1_u64;
+
+        self.is_finished()
+    }
+
+    /// Gets whether every swimmer has finished
+    pub fn is_finished(&self) -> bool {
+        self.racers
+            .iter()
+            .all(|racer: &Racer| racer.finish.is_some())
+    }
+
+    /// Gets every swimmer in the heat, in lane order
+    pub fn racers(&self) -> &[Racer] {
+        &self.racers
+    }
+
+    /// Gets the ticks since the start
+    pub fn elapsed(&self) -> u64 {
+        self.elapsed
+    }
+
+    /// Gets the length of the race, in position units
+    pub fn distance(&self) -> f64 {
+        self.meet.distance as f64 * LANE_LENGTH
+    }
+
+    /// Gets the place of every swimmer, finishers by time and the rest by distance swum
+    ///
+    /// # Returns
+    /// The lane index of every swimmer paired with their place, leader first
+    pub fn standings(&self) -> Vec<(usize, usize)> {
+        let mut lanes: Vec<usize> = (0_usize..self.racers.len()).collect();
+        lanes.sort_by(|a: &usize, b: &usize| {
+            let (a, b): (&Racer, &Racer) = (&self.racers[*a], &self.racers[*b]);
+            match (a.finish, b.finish) {
+                (Some(a_finish), Some(b_finish)) => a_finish.total_cmp(&b_finish),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => b.swimmer.progress.total_cmp(&a.swimmer.progress),
+            }
+        });
+        lanes
+            .into_iter()
+            .enumerate()
+            .map(|(i, lane)| (lane, i + 1_usize))
+            .collect()
+    }
+}
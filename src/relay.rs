@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+/// Swimmers in a relay team, each swims one leg
+pub const RELAY_SIZE: usize = 4;
+
+/// Lengths every member swims on their leg, an even number so each hands over at the left wall
+pub const LEG_LENGTHS: u64 = 2_u64;
+
+/// Bonus lengths credited to the team for every relay completed
+pub const RELAY_BONUS_LENGTHS: u64 = 8_u64;
+
+/// What happened at the end of a tick of a relay
+pub enum Handover {
+    Swimming,       // The current leg goes on
+    NextLeg(usize), // The leg is over, the swimmer at this index into the team dives in
+    Finished(u64),  // The last leg is over, the relay took this many ticks
+}
+
+/// Four swimmers sharing a lane, each swimming their leg in turn
+#[derive(Serialize, Deserialize)]
+pub struct Relay {
+    pub name: String,            // Name shown in the UI, e.g. "Relay A"
+    pub members: Vec<usize>,     // Indices into the team's swimmers, in leg order
+    leg: usize,                  // Index into `members` of the swimmer in the water
+    leg_lengths: u64,            // Lengths swum on the current leg
+    ticks: u64,                  // Ticks since the first leg of the current relay started
+    pub completed: u64,          // Relays swum to the end
+    pub last_ticks: Option<u64>, // Time of the last completed relay
+    pub best_ticks: Option<u64>, // Fastest completed relay
+}
+
+impl Relay {
+    /// Forms a relay team from the given swimmers
+    ///
+    /// # Arguments
+    /// * `name` - Name shown in the UI
+    /// * `members` - Indices into the team's swimmers, in leg order
+    ///
+    /// # Returns
+    /// A new relay with the first member about to start
+    pub fn new(name: String, members: Vec<usize>) -> Self {
+        Self {
+            name,
+            members,
+            leg: 0_usize,
+            leg_lengths: 0_u64,
+            ticks: 0_u64,
+            completed: 0_u64,
+            last_ticks: None,
+            best_ticks: None,
+        }
+    }
+
+    /// Gets the index into the team's swimmers of the member in the water
+    pub fn active_member(&self) -> usize {
+        self.members[self.leg.min(self.members.len() - 1_usize)]
+    }
+
+    /// Gets the leg being swum, starting at 1
+    pub fn leg(&self) -> usize {
+        self.leg + 1_usize
+    }
+
+    /// Gets the ticks since the first leg of the current relay started
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Gets how many lengths the member in the water has left to swim on their leg
+    pub fn lengths_to_hand_over(&self) -> u64 {
+        LEG_LENGTHS.saturating_sub(self.leg_lengths)
+    }
+
+    /// Counts ticks of the current leg and works out whether it is time to hand over
+    ///
+    /// # Arguments
+    /// * `ticks` - Ticks swum, one at a time while the game is open
+    /// * `lengths` - Lengths the member in the water completed in those ticks
+    ///
+    /// # Returns
+    /// Whether the leg goes on, the next member dives in, or the relay is over
+    pub fn record_ticks(&mut self, ticks: u64, lengths: u64) -> Handover {
+        self.ticks += ticks;
+        self.leg_lengths += lengths;
+        if self.leg_lengths < LEG_LENGTHS {
+            return Handover::Swimming;
+        }
+
+        self.leg_lengths = 0_u64;
+        self.leg += 1_usize;
+        if self.leg < self.members.len() {
+            return Handover::NextLeg(self.members[self.leg]);
+        }
+
+        // Everyone has swum their leg, start over with the first member
+        let ticks: u64 = self.ticks;
+        self.leg = 0_usize;
+        self.ticks = 0_u64;
+        self.completed += 1_u64;
+        self.last_ticks = Some(ticks);
+        self.best_ticks = Some(self.best_ticks.map_or(ticks, |best: u64| best.min(ticks)));
+        Handover::Finished(ticks)
+    }
+}
@@ -0,0 +1,118 @@
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// The game's single source of randomness
+///
+/// Every name, stroke, perk offer, meet and race result is drawn from it, so
+/// two games started from the same seed that are given the same actions play
+/// out identically. It is saved as its seed and how far into the stream it
+/// has drawn, so a resumed game carries on exactly where it left off.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "RngState", into = "RngState")]
+pub struct GameRng {
+    seed: u64,          // Seed the stream was started from
+    stream: ChaCha8Rng, // Generator positioned at the next value to draw
+}
+
+/// How a `GameRng` is written to the save file
+#[derive(Serialize, Deserialize)]
+struct RngState {
+    seed: u64,      // Seed the stream was started from
+    position: u128, // Words drawn from the stream so far
+}
+
+impl Default for GameRng {
+    /// Starts a stream from a random seed, for games started without one
+    fn default() -> Self {
+        Self::seeded(rand::random::<u64>())
+    }
+}
+
+impl GameRng {
+    /// Starts a stream from a seed
+    ///
+    /// # Arguments
+    /// * `seed` - The seed, equal seeds give equal streams
+    ///
+    /// # Returns
+    /// A GameRng at the start of the seed's stream
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seed,
+            stream: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Gets the seed the stream was started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.stream.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.stream.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.stream.fill_bytes(dst)
+    }
+}
+
+impl From<RngState> for GameRng {
+    fn from(state: RngState) -> Self {
+        let mut rng: GameRng = Self::seeded(state.seed);
+        rng.stream.set_word_pos(state.position);
+        rng
+    }
+}
+
+impl From<GameRng> for RngState {
+    fn from(rng: GameRng) -> Self {
+        Self {
+            seed: rng.seed,
+            position: rng.stream.get_word_pos(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_the_same_stream() {
+        let (mut first, mut second): (GameRng, GameRng) =
+            (GameRng::seeded(9_u64), GameRng::seeded(9_u64));
+
+        for _ in 0_u32..100_u32 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_ne!(
+            GameRng::seeded(10_u64).next_u64(),
+            GameRng::seeded(9_u64).next_u64()
+        );
+    }
+
+    #[test]
+    fn saved_stream_carries_on_where_it_left_off() {
+        let mut rng: GameRng = GameRng::seeded(9_u64);
+        for _ in 0_u32..37_u32 {
+            rng.next_u32();
+        }
+
+        let saved: String = serde_json::to_string(&rng).unwrap();
+        let mut resumed: GameRng = serde_json::from_str::<GameRng>(&saved).unwrap();
+
+        assert!(saved.contains("\"position\":37"));
+        assert_eq!(resumed.seed(), 9_u64);
+        for _ in 0_u32..100_u32 {
+            assert_eq!(resumed.next_u64(), rng.next_u64());
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use miette::{Diagnostic, NamedSource, SourceOffset, SourceSpan};
+use thiserror::Error;
+
+/// Error raised when the save file exists but cannot be parsed
+#[derive(Debug, Error, Diagnostic)]
+#[error("Save file is corrupt")]
+#[diagnostic(
+    code(save::corrupt),
+    help("Fix the highlighted field or delete the save file to start a new game")
+)]
+pub struct CorruptSaveError {
+    #[source_code]
+    source_code: NamedSource<String>,
+    #[label("{reason}")]
+    span: SourceSpan,
+    reason: String,
+}
+
+impl CorruptSaveError {
+    /// Builds a diagnostic pointing at the location serde_json failed on
+    ///
+    /// # Arguments
+    /// * `path` - Path of the save file, used as the source name
+    /// * `contents` - Raw contents of the save file
+    /// * `error` - The parse error returned by serde_json
+    ///
+    /// # Returns
+    /// A new CorruptSaveError
+    pub fn new(path: &str, contents: String, error: &serde_json::Error) -> Self {
+        // serde_json reports 1-based lines and columns, with column 0 meaning "start of line"
+        let offset: SourceOffset =
+            SourceOffset::from_location(&contents, error.line(), error.column().max(1_usize));
+        // Strip serde_json's " at line X column Y" suffix since the label already shows it
+        let full_message: String = error.to_string();
+        let reason: String = match full_message.rfind(" at line ") {
+            Some(index) => full_message[..index].to_string(),
+            None => full_message,
+        };
+
+        Self {
+            source_code: NamedSource::new(path, contents),
+            span: SourceSpan::new(offset, 1_usize),
+            reason,
+        }
+    }
+}
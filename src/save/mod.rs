@@ -0,0 +1,226 @@
+//! Save module for persisting the game state between sessions
+
+mod error;
+
+pub use crate::save::error::CorruptSaveError;
+
+use miette::{IntoDiagnostic, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Directory the save file and its backups are kept in, the working directory
+pub const SAVE_DIR: &str = "";
+
+/// Name of the save file
+pub const SAVE_PATH: &str = "save.json";
+
+/// Temporary file the save is written to before being renamed into place
+const TEMP_SAVE_PATH: &str = "save.json.tmp";
+
+/// Where a corrupt save is moved when a backup is restored over it
+const CORRUPT_SAVE_PATH: &str = "save.corrupt.json";
+
+/// A rotating backup of the save file
+pub struct Backup {
+    pub path: PathBuf,                // Location of the backup file
+    pub modified: Option<SystemTime>, // When the backup was written, if known
+}
+
+/// Gets the path of the backup with the given number (1 is the newest)
+///
+/// # Arguments
+/// * `dir` - Directory the save files are kept in
+/// * `number` - The backup number
+///
+/// # Returns
+/// The path of the backup file
+fn backup_path(dir: &Path, number: usize) -> PathBuf {
+    dir.join(format!("save.{}.json", number))
+}
+
+/// Loads a saved state from the given file
+///
+/// # Arguments
+/// * `path` - The save file to read
+///
+/// # Returns
+/// A Result containing the saved state, `None` if the file does not exist,
+/// or a `CorruptSaveError` diagnostic if the file cannot be parsed
+pub fn load_save<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents: String = fs::read_to_string(path).into_diagnostic()?;
+    match serde_json::from_str::<T>(&contents) {
+        Ok(state) => Ok(Some(state)),
+        Err(error) => {
+            Err(CorruptSaveError::new(&path.display().to_string(), contents, &error).into())
+        }
+    }
+}
+
+/// Writes the given state to the save file
+///
+/// The state is written to a temporary file and flushed to disk first, then
+/// renamed over the save file, so the save file is never left half-written.
+///
+/// # Arguments
+/// * `dir` - Directory the save files are kept in
+/// * `state` - The state to serialize
+///
+/// # Returns
+/// A Result indicating success or failure
+pub fn write_save<T: Serialize>(dir: &Path, state: &T) -> Result<()> {
+    let contents: String = serde_json::to_string_pretty(state).into_diagnostic()?;
+    let temp_path: PathBuf = dir.join(TEMP_SAVE_PATH);
+    let mut file: File = File::create(&temp_path).into_diagnostic()?;
+    file.write_all(contents.as_bytes()).into_diagnostic()?;
+    file.sync_all().into_diagnostic()?;
+
+    fs::rename(&temp_path, dir.join(SAVE_PATH)).into_diagnostic()?;
+    Ok(())
+}
+
+/// Copies the save file into the newest backup, shifting the older ones down
+///
+/// Called once when a session starts rather than on every save, so the
+/// backups reach back over the last few sessions instead of minutes.
+///
+/// # Arguments
+/// * `dir` - Directory the save files are kept in
+/// * `backup_count` - Number of backups to keep
+///
+/// # Returns
+/// A Result indicating success or failure
+pub fn rotate_backups(dir: &Path, backup_count: usize) -> Result<()> {
+    let save_path: PathBuf = dir.join(SAVE_PATH);
+    if backup_count == 0_usize || !save_path.exists() {
+        return Ok(());
+    }
+
+    // Shift every backup down by one, dropping the oldest
+    for number in (1_usize..backup_count).rev() {
+        let from: PathBuf = backup_path(dir, number);
+        if from.exists() {
+            fs::rename(&from, backup_path(dir, number + 1_usize)).into_diagnostic()?;
+        }
+    }
+    // Copy rather than move so a save file exists at every point in time
+    fs::copy(&save_path, backup_path(dir, 1_usize)).into_diagnostic()?;
+    Ok(())
+}
+
+/// Lists the backups that exist on disk, newest first
+///
+/// # Arguments
+/// * `dir` - Directory the save files are kept in
+/// * `backup_count` - Number of backups that are kept
+///
+/// # Returns
+/// The existing backups
+pub fn list_backups(dir: &Path, backup_count: usize) -> Vec<Backup> {
+    (1_usize..=backup_count)
+        .map(|number: usize| backup_path(dir, number))
+        .filter(|path: &PathBuf| path.exists())
+        .map(|path: PathBuf| {
+            let modified: Option<SystemTime> = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            Backup { path, modified }
+        })
+        .collect()
+}
+
+/// Moves the corrupt save file aside so the next session does not rotate it into the backups
+///
+/// # Arguments
+/// * `dir` - Directory the save files are kept in
+///
+/// # Returns
+/// A Result indicating success or failure
+pub fn quarantine_corrupt_save(dir: &Path) -> Result<()> {
+    let save_path: PathBuf = dir.join(SAVE_PATH);
+    if save_path.exists() {
+        fs::rename(&save_path, dir.join(CORRUPT_SAVE_PATH)).into_diagnostic()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Makes an empty directory of its own for a test to keep save files in
+    pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("swimming-idle-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Reads a save file written by a test
+    fn read(path: PathBuf) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn save_is_written_in_place_of_the_temp_file() {
+        let dir: PathBuf = scratch_dir("write");
+
+        write_save(&dir, &1_u32).unwrap();
+        write_save(&dir, &2_u32).unwrap();
+
+        assert_eq!(load_save::<u32>(&dir.join(SAVE_PATH)).unwrap(), Some(2_u32));
+        assert!(!dir.join(TEMP_SAVE_PATH).exists());
+        assert!(list_backups(&dir, 3_usize).is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn backups_rotate_down_and_drop_the_oldest() {
+        let dir: PathBuf = scratch_dir("rotate");
+
+        for session in 1_u32..=4_u32 {
+            write_save(&dir, &session).unwrap();
+            rotate_backups(&dir, 2_usize).unwrap();
+        }
+
+        assert_eq!(read(dir.join(SAVE_PATH)), "4");
+        assert_eq!(read(backup_path(&dir, 1_usize)), "4");
+        assert_eq!(read(backup_path(&dir, 2_usize)), "3");
+        assert!(!backup_path(&dir, 3_usize).exists());
+        let backups: Vec<Backup> = list_backups(&dir, 2_usize);
+        assert_eq!(backups[0].path, backup_path(&dir, 1_usize));
+        assert_eq!(backups.len(), 2_usize);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn nothing_rotates_without_a_save() {
+        let dir: PathBuf = scratch_dir("no-save");
+
+        rotate_backups(&dir, 3_usize).unwrap();
+
+        assert!(list_backups(&dir, 3_usize).is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn corrupt_save_is_quarantined() {
+        let dir: PathBuf = scratch_dir("quarantine");
+        fs::write(dir.join(SAVE_PATH), "{ not json").unwrap();
+
+        assert!(load_save::<u32>(&dir.join(SAVE_PATH)).is_err());
+        quarantine_corrupt_save(&dir).unwrap();
+
+        assert!(!dir.join(SAVE_PATH).exists());
+        assert_eq!(read(dir.join(CORRUPT_SAVE_PATH)), "{ not json");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+use crate::modifiers::Modifiers;
+use crate::wallet::Wallet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Permanent upgrades that can only be bought with points
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ShopItem {
+    Goggles,
+    Sponsorship,
+    Scouting,
+}
+
+impl ShopItem {
+    /// Every item in the order it is listed in the shop
+    pub const ALL: [ShopItem; 3] = [ShopItem::Goggles, ShopItem::Sponsorship, ShopItem::Scouting];
+
+    /// Gets the display name of the item
+    pub fn name(self) -> &'static str {
+        match self {
+            ShopItem::Goggles => "Pro Goggles",
+            ShopItem::Sponsorship => "Sponsorship Deal",
+            ShopItem::Scouting => "Talent Scouting",
+        }
+    }
+
+    /// Gets a short description of what one level of the item does
+    pub fn description(self) -> &'static str {
+        match self {
+            ShopItem::Goggles => "+10% speed for every swimmer",
+            ShopItem::Sponsorship => "-5% on every swimmer upgrade",
+            ShopItem::Scouting => "+0.2 starting speed for new hires",
+        }
+    }
+
+    /// Gets the price of the first level, in points
+    fn base_price(self) -> f64 {
+        match self {
+            ShopItem::Goggles => 5_f64,
+            ShopItem::Sponsorship => 8_f64,
+            ShopItem::Scouting => 3_f64,
+        }
+    }
+
+    /// Gets how much the price grows with each level bought
+    fn price_growth(self) -> f64 {
+        match self {
+            ShopItem::Goggles => 1.6_f64,
+            ShopItem::Sponsorship => 1.8_f64,
+            ShopItem::Scouting => 1.5_f64,
+        }
+    }
+}
+
+/// Levels of the permanent upgrades bought so far
+#[derive(Default, Serialize, Deserialize)]
+pub struct Shop {
+    levels: BTreeMap<ShopItem, u32>,
+}
+
+impl Shop {
+    /// Gets how many levels of an item have been bought
+    ///
+    /// # Arguments
+    /// * `item` - The shop item
+    ///
+    /// # Returns
+    /// The current level, 0 if never bought
+    pub fn level(&self, item: ShopItem) -> u32 {
+        self.levels.get(&item).copied().unwrap_or(0_u32)
+    }
+
+    /// Gets the price of the next level of an item
+    ///
+    /// # Arguments
+    /// * `item` - The shop item
+    ///
+    /// # Returns
+    /// The price in points
+    pub fn price(&self, item: ShopItem) -> u64 {
+        (item.base_price() * item.price_growth().powi(self.level(item) as i32)).ceil() as u64
+    }
+
+    /// Buys the next level of an item with points from the wallet
+    ///
+    /// # Arguments
+    /// * `item` - The shop item to buy
+    /// * `wallet` - The team wallet to pay from
+    ///
+    /// # Returns
+    /// `true` if successful, `false` if not enough points
+    pub fn buy(&mut self, item: ShopItem, wallet: &mut Wallet) -> bool {
+        if !wallet.spend_points(self.price(item)) {
+            return false;
+        }
+        *self.levels.entry(item).or_insert(0_u32) += 1_u32;
+        true
+    }
+
+    /// Applies the effects of every level bought to the modifiers
+    ///
+    /// # Arguments
+    /// * `modifiers` - The modifiers to update
+    pub fn apply(&self, modifiers: &mut Modifiers) {
+        modifiers.speed_multiplier *= 1_f64 + 0.1_f64 * self.level(ShopItem::Goggles) as f64;
+        modifiers.upgrade_cost_multiplier *=
+            0.95_f64.powi(self.level(ShopItem::Sponsorship) as i32);
+        modifiers.hire_speed_bonus += 0.2_f64 * self.level(ShopItem::Scouting) as f64;
+    }
+}
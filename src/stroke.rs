@@ -0,0 +1,164 @@
+use crate::rng::GameRng;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Swimming stroke, which changes how fast a swimmer goes and how quickly they tire
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stroke {
+    #[default]
+    Freestyle,
+    Backstroke,
+    Breaststroke,
+    Butterfly,
+    Medley, // A length of each of the other strokes in turn
+}
+
+impl Stroke {
+    /// Every stroke in the order it is cycled through when retraining
+    pub const ALL: [Stroke; 5] = [
+        Stroke::Freestyle,
+        Stroke::Backstroke,
+        Stroke::Breaststroke,
+        Stroke::Butterfly,
+        Stroke::Medley,
+    ];
+
+    /// Order of the legs swum by a medley swimmer
+    const MEDLEY_ORDER: [Stroke; 4] = [
+        Stroke::Butterfly,
+        Stroke::Backstroke,
+        Stroke::Breaststroke,
+        Stroke::Freestyle,
+    ];
+
+    /// Picks a stroke at random for a newly hired swimmer
+    ///
+    /// # Arguments
+    /// * `rng` - The game's random number generator
+    ///
+    /// # Returns
+    /// A random stroke
+    pub fn random(rng: &mut GameRng) -> Self {
+        Self::ALL[rng.random_range(0_usize..Self::ALL.len())]
+    }
+
+    /// Gets the display name of the stroke
+    pub fn name(self) -> &'static str {
+        match self {
+            Stroke::Freestyle => "Freestyle",
+            Stroke::Backstroke => "Backstroke",
+            Stroke::Breaststroke => "Breaststroke",
+            Stroke::Butterfly => "Butterfly",
+            Stroke::Medley => "Medley",
+        }
+    }
+
+    /// Gets a short description of how the stroke swims
+    pub fn description(self) -> &'static str {
+        match self {
+            Stroke::Freestyle => "Fast and steady",
+            Stroke::Backstroke => "A little slower, tires less",
+            Stroke::Breaststroke => "Slowest, barely tires",
+            Stroke::Butterfly => "Fastest, tires quickly",
+            Stroke::Medley => "A length of each stroke in turn, fly first",
+        }
+    }
+
+    /// Gets the stroke actually swum on a length
+    ///
+    /// # Arguments
+    /// * `length` - Number of lengths completed before this one
+    ///
+    /// # Returns
+    /// The medley leg for a medley swimmer, the stroke itself for the others
+    pub fn leg(self, length: u64) -> Self {
+        match self {
+            Stroke::Medley => {
+                Self::MEDLEY_ORDER[(length % Self::MEDLEY_ORDER.len() as u64) as usize]
+            }
+            stroke => stroke,
+        }
+    }
+
+    /// Gets how many lengths it takes to be back at the same wall swimming the same leg
+    pub fn cycle_lengths(self) -> u64 {
+        match self {
+            Stroke::Medley => Self::MEDLEY_ORDER.len() as u64,
+            _ => 2_u64,
+        }
+    }
+
+    /// Gets the multiplier applied to the swimmer's speed
+    ///
+    /// Medley swimmers swim each length at the speed of its leg, this is their average.
+    pub fn speed_multiplier(self) -> f64 {
+        match self {
+            Stroke::Freestyle => 1_f64,
+            Stroke::Backstroke => 0.9_f64,
+            Stroke::Breaststroke => 0.8_f64,
+            Stroke::Butterfly => 1.15_f64,
+            Stroke::Medley => Self::medley_average(Self::speed_multiplier),
+        }
+    }
+
+    /// Gets the multiplier applied to the speed lost over a length
+    ///
+    /// Medley swimmers tire over each length as its leg does, this is their average.
+    pub fn fatigue_multiplier(self) -> f64 {
+        match self {
+            Stroke::Freestyle => 1_f64,
+            Stroke::Backstroke => 0.8_f64,
+            Stroke::Breaststroke => 0.6_f64,
+            Stroke::Butterfly => 1.6_f64,
+            Stroke::Medley => Self::medley_average(Self::fatigue_multiplier),
+        }
+    }
+
+    /// Averages a property of the strokes over the legs of a medley
+    ///
+    /// # Arguments
+    /// * `property` - The property of a single stroke
+    ///
+    /// # Returns
+    /// The average over one length of every leg
+    fn medley_average(property: fn(Stroke) -> f64) -> f64 {
+        Self::MEDLEY_ORDER
+            .iter()
+            .map(|stroke: &Stroke| property(*stroke))
+            .sum::<f64>()
+            / Self::MEDLEY_ORDER.len() as f64
+    }
+
+    /// Gets the animation frames of the swimmer's body, drawn next to the arrow
+    ///
+    /// A medley swimmer is drawn with the stroke of the leg they are on, see `leg`.
+    ///
+    /// # Returns
+    /// The characters to cycle through while swimming
+    pub fn frames(self) -> [char; 2] {
+        match self.leg(0_u64) {
+            Stroke::Backstroke => ['o', '°'],
+            Stroke::Breaststroke => ['v', '^'],
+            Stroke::Butterfly => ['w', 'W'],
+            _ => ['-', '='],
+        }
+    }
+
+    /// Gets the next stroke in `ALL`, wrapping around
+    pub fn next(self) -> Self {
+        let index: usize = Self::ALL
+            .iter()
+            .position(|stroke| *stroke == self)
+            .unwrap_or(0_usize);
+        Self::ALL[(index + 1_usize) % Self::ALL.len()]
+    }
+
+    /// Gets the previous stroke in `ALL`, wrapping around
+    pub fn previous(self) -> Self {
+        let index: usize = Self::ALL
+            .iter()
+            .position(|stroke| *stroke == self)
+            .unwrap_or(0_usize);
+        Self::ALL[(index + Self::ALL.len() - 1_usize) % Self::ALL.len()]
+    }
+}
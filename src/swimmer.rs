@@ -1,93 +1,1017 @@
+use crate::game::FRAME_DURATION_MS;
+use crate::modifiers::Modifiers;
+use crate::perks::{Perk, PerkEffect};
+use crate::race::{medal, ordinal, Medals};
+use crate::rng::GameRng;
+use crate::stroke::Stroke;
+use crate::utils::unix_timestamp;
+use crate::wallet::Wallet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Distance from one end of the pool to the other, in position units
+pub const LANE_LENGTH: f64 = 100_f64;
+
+/// Fraction of its speed a swimmer without stamina training keeps at the end of a length
+const BASE_STAMINA: f64 = 0.75_f64;
+
+/// Ticks lost at each wall by a swimmer without turn training
+const BASE_TURN_TICKS: f64 = 6_f64;
+
+/// Ticks lost starting from rest by a swimmer without start training
+const BASE_REACTION_TICKS: f64 = 15_f64;
+
+/// Lengths the first retraining into another stroke costs, doubling with each retraining
+const BASE_RETRAIN_COST: f64 = 50_f64;
+
+/// Energy of a fully rested swimmer
+const MAX_ENERGY: f64 = 1_f64;
+
+/// Energy used up by every length swum
+const ENERGY_PER_LENGTH: f64 = 0.02_f64;
+
+/// Energy regained by a resting swimmer every tick, a full recovery takes about a minute
+const ENERGY_RECOVERY_PER_TICK: f64 = 0.0005_f64;
+
+/// Energy below which a swimmer starts to slow down
+const LOW_ENERGY: f64 = 0.3_f64;
+
+/// Fraction of their speed an exhausted swimmer can still manage
+const EXHAUSTED_SPEED: f64 = 0.4_f64;
+
+/// XP needed to go from level 1 to level 2, each level after needs this much more than the last
+const XP_PER_LEVEL: f64 = 20_f64;
+
+/// Number of upgrade history entries kept per swimmer
+const MAX_HISTORY: usize = 20;
+
+/// Number of speed samples kept per swimmer for the sparkline
+pub const MAX_SPEED_SAMPLES: usize = 60;
+
+/// Gets the energy swimmers from saves without energy start with
+fn full_energy() -> f64 {
+    MAX_ENERGY
+}
+
+/// A swimmer attribute that can be upgraded on its own
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Stat {
+    Speed,    // Top speed at the start of each length
+    Stamina,  // How much of that speed is kept until the far wall
+    Turns,    // Time lost turning at each wall
+    Reaction, // Time lost starting from rest
+}
+
+impl Stat {
+    /// Every stat in the order it is listed in the upgrade menu
+    pub const ALL: [Stat; 4] = [Stat::Speed, Stat::Stamina, Stat::Turns, Stat::Reaction];
+
+    /// Gets the display name of the stat
+    pub fn name(self) -> &'static str {
+        match self {
+            Stat::Speed => "Speed",
+            Stat::Stamina => "Stamina",
+            Stat::Turns => "Turns",
+            Stat::Reaction => "Start Reaction",
+        }
+    }
+
+    /// Gets a short description of what upgrading the stat does
+    pub fn description(self) -> &'static str {
+        match self {
+            Stat::Speed => "+0.5 top speed",
+            Stat::Stamina => "Less slowdown towards the far wall",
+            Stat::Turns => "-15% time lost at each wall",
+            Stat::Reaction => "-20% time lost starting from rest",
+        }
+    }
+
+    /// Gets the price of the first upgrade, for stats priced by level
+    fn base_cost(self) -> f64 {
+        match self {
+            Stat::Speed => 10_f64,
+            Stat::Stamina => 15_f64,
+            Stat::Turns => 12_f64,
+            Stat::Reaction => 8_f64,
+        }
+    }
+
+    /// Gets how much the price grows with each upgrade, for stats priced by level
+    fn cost_growth(self) -> f64 {
+        match self {
+            Stat::Speed => 1.2_f64,
+            Stat::Stamina => 1.35_f64,
+            Stat::Turns => 1.3_f64,
+            Stat::Reaction => 1.25_f64,
+        }
+    }
+}
+
+/// Something that was bought or picked for a swimmer, kept for their history
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UpgradeRecord {
+    pub at: u64,             // Unix timestamp of when it happened
+    pub description: String, // What happened, e.g. "Speed upgraded to 2.2"
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Swimmer {
-    pub name: String,            // Name of the swimmer
-    pub progress: usize,         // Total progress made by the swimmer
-    pub speed: f64, // Speed of the swimmer (changed from usize to f64 for fractional speeds)
-    pub lengths: i32, // Number of lengths completed by the swimmer
+    pub name: String,  // Name of the swimmer
+    pub progress: f64, // Total distance swum by the swimmer
+    pub speed: f64,    // Speed of the swimmer (changed from usize to f64 for fractional speeds)
+    #[serde(default)]
+    pub lifetime_lengths: u64, // Number of lengths completed by the swimmer, never spent
     pub upgrade_cost: usize, // Cost (in lengths) to upgrade the swimmer's speed
-    pub position: usize, // Position in the current lane (0-100)
+    pub position: f64, // Position in the current lane (0-LANE_LENGTH)
     pub direction: bool, // true = right, false = left
     pub upgrade_multiplier: f64, // Multiplier for upgrade costs
+    #[serde(default)]
+    pub stroke: Stroke, // Stroke the swimmer swims, assigned at random when hired
+    #[serde(default)]
+    retrains: u32, // Number of times the swimmer was retrained into another stroke
+    #[serde(default)]
+    stat_levels: BTreeMap<Stat, u32>, // Upgrades bought for every stat except speed
+    #[serde(default)]
+    xp: f64, // Experience earned from lengths swum, never spent
+    #[serde(default)]
+    perks: Vec<Perk>, // Perks picked on level-ups
+    #[serde(default)]
+    perk_offer: Vec<Perk>, // Perks rolled for the next unclaimed level-up
+    #[serde(default = "full_energy")]
+    energy: f64, // Drains with every length, from 0 (exhausted) to 1 (fully rested)
+    #[serde(default)]
+    pub resting: bool, // Benched out of the pool, recovering energy instead of swimming
+    #[serde(default)]
+    history: Vec<UpgradeRecord>, // Most recent upgrades, retrains and perks, oldest first
+    #[serde(default)]
+    speed_samples: VecDeque<f64>, // Average distance per tick over recent sample intervals
+    #[serde(default)]
+    pub medals: Medals, // Medals won at meets
+    #[serde(default)]
+    lengths_swum: u64, // Lengths swum wall to wall, unscaled by the pool length, picks the medley leg
+    #[serde(default, rename = "lengths", skip_serializing)]
+    legacy_lengths: Option<i64>, // Spending balance kept on the swimmer by saves from before the team wallet
+    #[serde(skip)]
+    sampled_progress: f64, // Progress at the last speed sample
+    #[serde(skip)]
+    pause: f64, // Ticks left before the swimmer moves again, from a turn or a start
 }
 
 impl Swimmer {
-    /// Creates a new swimmer with the given name and speed
+    /// Creates a new swimmer with the given name and speed, swimming a random stroke
     ///
     /// # Arguments
     /// * `name` - The swimmer's name
     /// * `speed` - The swimmer's initial speed
+    /// * `rng` - The game's random number generator, picks the stroke
     ///
     /// # Returns
     /// A new Swimmer instance
-    pub fn new(name: &str, speed: f64) -> Self {
-        Self {
+    pub fn new(name: &str, speed: f64, rng: &mut GameRng) -> Self {
+        let mut swimmer: Swimmer = Self {
             name: name.to_string(),
-            progress: 0_usize,
+            progress: 0_f64,
             speed,
-            lengths: 0_i32,
+            lifetime_lengths: 0_u64,
             upgrade_cost: 10_usize,      // Starting upgrade cost
-            position: 0_usize,           // Always start at far left
+            position: 0_f64,             // Always start at far left
             direction: true,             // Always start moving right
             upgrade_multiplier: 1.2_f64, // Reduced from 1.5 to slow progression
+            stroke: Stroke::random(rng),
+            retrains: 0_u32,
+            stat_levels: BTreeMap::new(),
+            xp: 0_f64,
+            perks: Vec::new(),
+            perk_offer: Vec::new(),
+            energy: MAX_ENERGY,
+            resting: false,
+            history: Vec::new(),
+            speed_samples: VecDeque::new(),
+            medals: Medals::default(),
+            lengths_swum: 0_u64,
+            legacy_lengths: None,
+            sampled_progress: 0_f64,
+            pause: 0_f64,
+        };
+        swimmer.start();
+        swimmer
+    }
+
+    /// Takes the spending balance a save from before the team wallet kept on the swimmer
+    ///
+    /// The balance is also the best known count of the lengths the swimmer
+    /// has swum, so their lifetime count starts from it.
+    ///
+    /// # Returns
+    /// The balance to bank in the team wallet, clamped to zero as overspending
+    /// could drive it negative, or 0 for swimmers from newer saves
+    pub fn take_legacy_lengths(&mut self) -> u64 {
+        let lengths: u64 = self.legacy_lengths.take().unwrap_or(0_i64).max(0_i64) as u64;
+        self.lifetime_lengths = self.lifetime_lengths.max(lengths);
+        lengths
+    }
+
+    /// Holds the swimmer on the wall for their start reaction time, as when starting from rest
+    pub fn start(&mut self) {
+        self.pause = self.reaction_ticks();
+        self.sampled_progress = self.progress;
+    }
+
+    /// Copies the swimmer onto the starting blocks of a race, keeping their stats and energy
+    ///
+    /// # Returns
+    /// A copy at the left wall with no distance swum, waiting out their start reaction
+    pub fn on_blocks(&self) -> Swimmer {
+        let mut racer: Swimmer = self.clone();
+        racer.progress = 0_f64;
+        racer.lifetime_lengths = 0_u64;
+        racer.lengths_swum = 0_u64;
+        racer.position = 0_f64;
+        racer.direction = true;
+        racer.resting = false;
+        racer.start();
+        racer
+    }
+
+    /// Records where the swimmer finished at a meet, awarding a medal on the podium
+    ///
+    /// # Arguments
+    /// * `place` - Finishing place, starting at 1
+    /// * `meet_name` - Name of the meet
+    pub fn record_race(&mut self, place: usize, meet_name: &str) {
+        self.medals.award(place);
+        let description: String = format!(
+            "Finished {} at the {} {}",
+            ordinal(place),
+            meet_name,
+            medal(place)
+        );
+        self.record(description.trim_end().to_string());
+    }
+
+    /// Adds an entry to the swimmer's history, dropping the oldest once it is full
+    ///
+    /// # Arguments
+    /// * `description` - What happened
+    fn record(&mut self, description: String) {
+        self.history.push(UpgradeRecord {
+            at: unix_timestamp(),
+            description,
+        });
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0_usize);
         }
     }
 
+    /// Gets the swimmer's history, oldest first
+    pub fn history(&self) -> &[UpgradeRecord] {
+        &self.history
+    }
+
+    /// Records the swimmer's average speed since the last sample
+    ///
+    /// # Arguments
+    /// * `ticks` - Ticks since the last sample
+    pub fn sample_speed(&mut self, ticks: u64) {
+        let sample: f64 = (self.progress - self.sampled_progress) / ticks.max(1_u64) as f64;
+        self.sampled_progress = self.progress;
+        self.speed_samples.push_back(sample.max(0_f64));
+        if self.speed_samples.len() > MAX_SPEED_SAMPLES {
+            self.speed_samples.pop_front();
+        }
+    }
+
+    /// Gets the recent speed samples, oldest first, in distance per tick
+    pub fn speed_samples(&self) -> &VecDeque<f64> {
+        &self.speed_samples
+    }
+
+    /// Gets how many lengths a minute the swimmer has averaged over the recent speed samples
+    pub fn lengths_per_minute(&self) -> f64 {
+        if self.speed_samples.is_empty() {
+            return 0_f64;
+        }
+        let average: f64 = self.speed_samples.iter().sum::<f64>() / self.speed_samples.len() as f64;
+        let ticks_per_minute: f64 = 60_000_f64 / FRAME_DURATION_MS as f64;
+        average * ticks_per_minute / LANE_LENGTH
+    }
+
     /// Updates the swimmer's position and counts lengths
-    pub fn swim(&mut self) {
-        // Add to overall progress
-        self.progress += self.speed.round() as usize;
+    ///
+    /// # Arguments
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    ///
+    /// # Returns
+    /// The number of lengths completed during this tick
+    pub fn swim(&mut self, modifiers: &Modifiers) -> u64 {
+        self.swim_ticks(1_u64, modifiers)
+    }
 
-        // Move the swimmer position
-        if self.direction {
-            // Moving right
-            self.position += self.speed.round() as usize;
-            if self.position >= 100_usize {
-                // Reached the right end
-                self.lengths += 1_i32; // Count a length
-
-                // Set position to exactly at the right edge
-                self.position = 100_usize;
-                // Change direction to start swimming back left
-                self.direction = false;
+    /// Advances the swimmer by many ticks at once without looping over them
+    ///
+    /// Lengths swum at the same energy band take the same time, a turn plus the
+    /// swim itself, so they are counted in one step. Only the few lengths where
+    /// a tiring swimmer slows down are stepped one at a time. This matters for
+    /// crediting offline progress where the tick count can be in the millions.
+    ///
+    /// # Arguments
+    /// * `ticks` - Number of `swim` ticks to simulate
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    ///
+    /// # Returns
+    /// The number of lengths completed during those ticks
+    pub fn swim_ticks(&mut self, ticks: u64, modifiers: &Modifiers) -> u64 {
+        // Resting swimmers stay out of the water and get their energy back
+        if self.resting {
+            self.recover(ticks);
+            return 0_u64;
+        }
+
+        // Sit out whatever is left of a turn or a start first, shortened by the starting blocks
+        let wall_time: f64 = modifiers.wall_time_multiplier;
+        let paused: f64 = (self.pause * wall_time).min(ticks as f64);
+        self.pause = (self.pause - paused / wall_time).max(0_f64);
+        let mut remaining: f64 = ticks as f64 - paused;
+
+        // A longer pool takes longer to cross, which looks like swimming slower down the lane
+        let scale: f64 = modifiers.length_scale as f64;
+
+        let mut lengths: u64 = 0_u64;
+        loop {
+            let stroke: Stroke = self.current_stroke();
+            let speed: f64 = self.effective_speed(modifiers) / scale;
+            if speed <= 0_f64 {
+                break;
             }
-        } else {
-            // Moving left
-            if self.position <= self.speed.round() as usize {
-                // Reached the left end
-                self.lengths += 1_i32; // Count a length
-
-                // Set position to exactly at the left edge
-                self.position = 0_usize;
-                // Change direction to start swimming back right
-                self.direction = true;
-            } else {
-                self.position -= self.speed.round() as usize;
+
+            // Finish the current length, or stay within it if there is not enough time
+            let covered: f64 = self.covered();
+            let to_wall: f64 = self.ticks_between(covered, LANE_LENGTH, speed, stroke);
+            if remaining < to_wall {
+                let reached: f64 = self.distance_after(covered, remaining, speed, stroke);
+                self.progress += reached - covered;
+                self.set_covered(reached);
+                break;
             }
+            remaining -= to_wall;
+            self.progress += LANE_LENGTH - covered;
+            self.touch_wall(1_u64, modifiers);
+            lengths += 1_u64;
+
+            // Turn at the wall just touched
+            let turn: f64 = self.turn_ticks() * wall_time;
+            if remaining < turn {
+                self.pause = (turn - remaining) / wall_time;
+                break;
+            }
+            remaining -= turn;
+
+            // At this energy band every cycle of lengths that ends back at the same wall on
+            // the same leg takes the same time, an out and back pair or a full medley
+            let cycle_lengths: u64 = self.stroke.cycle_lengths();
+            let cycle: f64 = (0_u64..cycle_lengths)
+                .map(|length: u64| {
+                    let leg: Stroke = self.stroke.leg(self.lengths_swum + length);
+                    let heading: bool = self.direction == length.is_multiple_of(2_u64);
+                    let leg_speed: f64 = self.speed_swimming(leg, heading, modifiers) / scale;
+                    self.ticks_between(0_f64, LANE_LENGTH, leg_speed, leg) + turn
+                })
+                .sum();
+            let cycles: u64 = ((remaining / cycle).floor() as u64)
+                .min(self.steady_lengths(modifiers) / cycle_lengths);
+            remaining -= cycles as f64 * cycle;
+            self.progress += (cycle_lengths * cycles) as f64 * LANE_LENGTH;
+            self.touch_wall(cycle_lengths * cycles, modifiers);
+            lengths += cycle_lengths * cycles;
+        }
+
+        self.credit_lengths(lengths, modifiers)
+    }
+
+    /// Works out how long the swimmer takes to touch the wall a number of times from where they are
+    ///
+    /// # Arguments
+    /// * `lengths` - Number of walls to touch, the first one at the end of the current length
+    /// * `modifiers` - Global modifiers from permanent upgrades and the pool
+    ///
+    /// # Returns
+    /// The whole ticks `swim_ticks` needs to get there, or `None` if the swimmer is resting or cannot move
+    pub fn ticks_to_swim(&self, lengths: u64, modifiers: &Modifiers) -> Option<u64> {
+        if self.resting {
+            return None;
+        }
+
+        // Swim a copy the way `swim_ticks` would, turning between lengths
+        let mut swimmer: Swimmer = self.clone();
+        let wall_time: f64 = modifiers.wall_time_multiplier;
+        let scale: f64 = modifiers.length_scale as f64;
+        let mut ticks: f64 = swimmer.pause * wall_time;
+        for length in 0_u64..lengths {
+            if length > 0_u64 {
+                ticks += swimmer.turn_ticks() * wall_time;
+            }
+            let stroke: Stroke = swimmer.current_stroke();
+            let speed: f64 = swimmer.effective_speed(modifiers) / scale;
+            if speed <= 0_f64 {
+                return None;
+            }
+            ticks += swimmer.ticks_between(swimmer.covered(), LANE_LENGTH, speed, stroke);
+            swimmer.touch_wall(1_u64, modifiers);
+        }
+        Some((ticks.ceil() as u64).max(1_u64))
+    }
+
+    /// Counts lengths swum just like lengths the swimmer has swum before, as when a relay goes round the same way again
+    ///
+    /// The swimmer ends up where they started with the same energy, so only the counts move on.
+    ///
+    /// # Arguments
+    /// * `lengths` - Lengths of the pool swum, whole cycles of the swimmer's stroke
+    /// * `modifiers` - Global modifiers from permanent upgrades and the pool
+    ///
+    /// # Returns
+    /// The number of lengths credited, counted as `swim_ticks` counts them
+    pub fn repeat_lengths(&mut self, lengths: u64, modifiers: &Modifiers) -> u64 {
+        self.progress += lengths as f64 * LANE_LENGTH;
+        self.lengths_swum += lengths;
+        self.credit_lengths(lengths, modifiers)
+    }
+
+    /// Gets what decides how the swimmer's next lengths go once they are lined up
+    ///
+    /// # Returns
+    /// The swimmer's energy and how many lengths into their stroke's cycle they are
+    pub fn cycle_state(&self) -> (f64, u64) {
+        (self.energy, self.lengths_swum % self.stroke.cycle_lengths())
+    }
+
+    /// Credits lengths of the pool to the swimmer's xp and lifetime count
+    ///
+    /// # Arguments
+    /// * `lengths` - Lengths of the pool swum
+    /// * `modifiers` - Global modifiers from permanent upgrades and the pool
+    ///
+    /// # Returns
+    /// The number of lengths earned, in 25 m lengths with perk bonuses
+    fn credit_lengths(&mut self, lengths: u64, modifiers: &Modifiers) -> u64 {
+        // Every length of a longer pool counts as that many 25 m lengths
+        let lengths: u64 = lengths * modifiers.length_scale;
+
+        // Perks can credit extra lengths for turns, which are earned but never swum
+        let bonus_lengths: u64 = self.bonus_lengths(lengths);
+        self.xp += lengths as f64 * self.xp_multiplier();
+        self.lifetime_lengths += lengths;
+        lengths + bonus_lengths
+    }
+
+    /// Counts the extra lengths perks credit for turning at the walls
+    ///
+    /// # Arguments
+    /// * `lengths` - Lengths just swum, not yet added to the lifetime count
+    ///
+    /// # Returns
+    /// The number of bonus lengths
+    fn bonus_lengths(&self, lengths: u64) -> u64 {
+        let before: u64 = self.lifetime_lengths;
+        let after: u64 = before + lengths;
+        self.perks
+            .iter()
+            .map(|perk: &Perk| match perk.effect {
+                PerkEffect::BonusLength { every } if every > 0_u64 => {
+                    after / every - before / every
+                }
+                _ => 0_u64,
+            })
+            .sum()
+    }
+
+    /// Counts lengths finished at the wall: turns the swimmer around and drains their energy
+    ///
+    /// # Arguments
+    /// * `lengths` - Number of lengths finished
+    /// * `modifiers` - Global modifiers from permanent upgrades and the pool
+    fn touch_wall(&mut self, lengths: u64, modifiers: &Modifiers) {
+        // Each length flips the direction, so an odd count ends facing the other way
+        if lengths % 2_u64 == 1_u64 {
+            self.direction = !self.direction;
         }
+        self.energy = (self.energy - lengths as f64 * self.energy_per_length(modifiers)).max(0_f64);
+        self.lengths_swum += lengths;
+        self.set_covered(0_f64);
     }
 
-    /// Attempts to upgrade the swimmer's speed using lengths as currency
+    /// Counts how many more lengths the swimmer can swim before tiredness changes their speed
+    ///
+    /// # Arguments
+    /// * `modifiers` - Global modifiers from permanent upgrades and the pool
+    ///
+    /// # Returns
+    /// The number of lengths, `u64::MAX` if the swimmer is already exhausted
+    fn steady_lengths(&self, modifiers: &Modifiers) -> u64 {
+        if self.energy <= 0_f64 {
+            u64::MAX
+        } else if self.energy >= LOW_ENERGY {
+            ((self.energy - LOW_ENERGY) / self.energy_per_length(modifiers)).floor() as u64 + 1_u64
+        } else {
+            // Every length swum while tired is slower than the one before
+            0_u64
+        }
+    }
+
+    /// Gets the energy used up by every length of the pool, after perks and facilities
+    ///
+    /// # Arguments
+    /// * `modifiers` - Global modifiers from permanent upgrades and the pool
+    fn energy_per_length(&self, modifiers: &Modifiers) -> f64 {
+        let saved: f64 = self
+            .perks
+            .iter()
+            .map(|perk: &Perk| match perk.effect {
+                PerkEffect::EnergySaver { reduction } => reduction,
+                _ => 0_f64,
+            })
+            .sum();
+        ENERGY_PER_LENGTH
+            * (1_f64 - saved).max(0.1_f64)
+            * modifiers.length_scale as f64
+            * modifiers.energy_multiplier
+    }
+
+    /// Gets the XP earned for every length, after perks
+    fn xp_multiplier(&self) -> f64 {
+        1_f64
+            + self
+                .perks
+                .iter()
+                .map(|perk: &Perk| match perk.effect {
+                    PerkEffect::XpBoost { bonus } => bonus,
+                    _ => 0_f64,
+                })
+                .sum::<f64>()
+    }
+
+    /// Gets the swimmer's level, which goes up as they earn XP
+    ///
+    /// Reaching level `n` takes `XP_PER_LEVEL * n * (n - 1) / 2` XP in total.
+    pub fn level(&self) -> u32 {
+        // Solve XP_PER_LEVEL * n * (n - 1) / 2 <= xp for the largest n
+        let scaled: f64 = 8_f64 * self.xp / XP_PER_LEVEL;
+        ((1_f64 + (1_f64 + scaled).sqrt()) / 2_f64).floor() as u32
+    }
+
+    /// Gets the total XP needed to reach a level
+    ///
+    /// # Arguments
+    /// * `level` - The level
+    ///
+    /// # Returns
+    /// The XP required
+    pub fn xp_for_level(level: u32) -> f64 {
+        XP_PER_LEVEL * level as f64 * (level as f64 - 1_f64) / 2_f64
+    }
+
+    /// Gets the swimmer's total XP
+    pub fn xp(&self) -> f64 {
+        self.xp
+    }
+
+    /// Gets the perks the swimmer has picked
+    pub fn perks(&self) -> &[Perk] {
+        &self.perks
+    }
+
+    /// Gets the perks on offer for a level-up that has not been claimed yet
+    pub fn perk_offer(&self) -> &[Perk] {
+        &self.perk_offer
+    }
+
+    /// Counts the level-ups whose perk has not been picked yet
+    pub fn unclaimed_perks(&self) -> u32 {
+        self.level()
+            .saturating_sub(1_u32)
+            .saturating_sub(self.perks.len() as u32)
+    }
+
+    /// Offers a fresh set of perks to pick from
+    ///
+    /// # Arguments
+    /// * `offer` - The perks rolled for the swimmer
+    pub fn offer_perks(&mut self, offer: Vec<Perk>) {
+        self.perk_offer = offer;
+    }
+
+    /// Picks one of the perks on offer
+    ///
+    /// # Arguments
+    /// * `index` - Index of the perk in the offer
+    ///
+    /// # Returns
+    /// The perk picked, or `None` if there is no such offer
+    pub fn choose_perk(&mut self, index: usize) -> Option<&Perk> {
+        if index >= self.perk_offer.len() || self.unclaimed_perks() == 0_u32 {
+            return None;
+        }
+        let perk: Perk = self.perk_offer.swap_remove(index);
+        self.perk_offer.clear();
+        self.record(format!("Learned {}", perk.name));
+        self.perks.push(perk);
+        self.perks.last()
+    }
+
+    /// Gets the fraction of their speed the swimmer can manage at their current energy
+    pub fn energy_factor(&self) -> f64 {
+        if self.energy >= LOW_ENERGY {
+            1_f64
+        } else {
+            EXHAUSTED_SPEED + (1_f64 - EXHAUSTED_SPEED) * self.energy / LOW_ENERGY
+        }
+    }
+
+    /// Gets whether the swimmer is tired enough to slow down
+    pub fn is_tired(&self) -> bool {
+        self.energy < LOW_ENERGY
+    }
+
+    /// Gets the swimmer's energy, from 0 (exhausted) to 1 (fully rested)
+    pub fn energy(&self) -> f64 {
+        self.energy
+    }
+
+    /// Lets the swimmer get their energy back while out of the water
+    ///
+    /// # Arguments
+    /// * `ticks` - Number of ticks spent out of the water
+    pub fn recover(&mut self, ticks: u64) {
+        self.energy = (self.energy + ticks as f64 * ENERGY_RECOVERY_PER_TICK).min(MAX_ENERGY);
+    }
+
+    /// Puts the swimmer on the left wall facing the far end, held for their start reaction
+    pub fn line_up(&mut self) {
+        self.position = 0_f64;
+        self.direction = true;
+        self.start();
+    }
+
+    /// Benches the swimmer to rest, or sends them back into the pool
+    ///
+    /// Either way the swimmer gets out at, or starts from, the left wall.
+    pub fn toggle_resting(&mut self) {
+        self.resting = !self.resting;
+        self.position = 0_f64;
+        self.direction = true;
+        self.pause = 0_f64;
+        if !self.resting {
+            self.start();
+        }
+    }
+
+    /// Gets how far into the current length the swimmer is
+    fn covered(&self) -> f64 {
+        if self.direction {
+            self.position
+        } else {
+            LANE_LENGTH - self.position
+        }
+    }
+
+    /// Moves the swimmer to the given distance into the current length
+    fn set_covered(&mut self, covered: f64) {
+        self.position = if self.direction {
+            covered
+        } else {
+            LANE_LENGTH - covered
+        };
+    }
+
+    /// Gets the stroke the swimmer is swimming on the current length, the leg of a medley
+    pub fn current_stroke(&self) -> Stroke {
+        self.stroke.leg(self.lengths_swum)
+    }
+
+    /// Gets how much speed the swimmer loses between one wall and the other
+    ///
+    /// # Arguments
+    /// * `stroke` - The stroke swum on the length
+    fn fatigue(&self, stroke: Stroke) -> f64 {
+        // Capped so even the most tiring stroke never comes to a standstill
+        ((1_f64 - self.stamina()) * stroke.fatigue_multiplier()).min(0.95_f64)
+    }
+
+    /// Works out how many ticks it takes to swim between two points of a length
+    ///
+    /// Speed drops linearly with the distance covered, so the time is the
+    /// integral of 1 / speed over the distance.
+    ///
+    /// # Arguments
+    /// * `from` - Distance into the length to start at
+    /// * `to` - Distance into the length to stop at
+    /// * `speed` - Effective speed at the start of the length
+    /// * `stroke` - The stroke swum on the length
+    ///
+    /// # Returns
+    /// The time in ticks
+    fn ticks_between(&self, from: f64, to: f64, speed: f64, stroke: Stroke) -> f64 {
+        let fatigue: f64 = self.fatigue(stroke);
+        if fatigue <= f64::EPSILON {
+            return (to - from) / speed;
+        }
+        let start: f64 = 1_f64 - fatigue * from / LANE_LENGTH;
+        let end: f64 = 1_f64 - fatigue * to / LANE_LENGTH;
+        LANE_LENGTH / (speed * fatigue) * (start / end).ln()
+    }
+
+    /// Works out how far into a length the swimmer gets after swimming for a while
+    ///
+    /// # Arguments
+    /// * `from` - Distance into the length to start at
+    /// * `ticks` - Time spent swimming, never enough to reach the far wall
+    /// * `speed` - Effective speed at the start of the length
+    /// * `stroke` - The stroke swum on the length
+    ///
+    /// # Returns
+    /// The distance into the length reached
+    fn distance_after(&self, from: f64, ticks: f64, speed: f64, stroke: Stroke) -> f64 {
+        let fatigue: f64 = self.fatigue(stroke);
+        let reached: f64 = if fatigue <= f64::EPSILON {
+            from + speed * ticks
+        } else {
+            let start: f64 = 1_f64 - fatigue * from / LANE_LENGTH;
+            LANE_LENGTH / fatigue * (1_f64 - start * (-speed * fatigue * ticks / LANE_LENGTH).exp())
+        };
+        reached.min(LANE_LENGTH)
+    }
+
+    /// Gets how many upgrades have been bought for a stat other than speed
+    ///
+    /// # Arguments
+    /// * `stat` - The stat
+    ///
+    /// # Returns
+    /// The number of upgrades bought
+    pub fn stat_level(&self, stat: Stat) -> u32 {
+        self.stat_levels.get(&stat).copied().unwrap_or(0_u32)
+    }
+
+    /// Gets the fraction of its speed the swimmer keeps at the end of a length
+    pub fn stamina(&self) -> f64 {
+        1_f64 - (1_f64 - BASE_STAMINA) * 0.8_f64.powi(self.stat_level(Stat::Stamina) as i32)
+    }
+
+    /// Gets the ticks the swimmer loses turning at each wall
+    pub fn turn_ticks(&self) -> f64 {
+        BASE_TURN_TICKS * 0.85_f64.powi(self.stat_level(Stat::Turns) as i32)
+    }
+
+    /// Gets the ticks the swimmer loses starting from rest
+    pub fn reaction_ticks(&self) -> f64 {
+        BASE_REACTION_TICKS * 0.8_f64.powi(self.stat_level(Stat::Reaction) as i32)
+    }
+
+    /// Attempts to upgrade one of the swimmer's stats using lengths from the team wallet
+    ///
+    /// # Arguments
+    /// * `stat` - The stat to upgrade
+    /// * `wallet` - The team wallet to pay from
+    /// * `modifiers` - Global modifiers from permanent upgrades
     ///
     /// # Returns
     /// `true` if successful, `false` if not enough lengths
-    pub fn upgrade(&mut self) -> bool {
-        // Check if player has enough lengths to upgrade
-        if self.lengths >= self.upgrade_cost as i32 {
-            self.lengths -= self.upgrade_cost as i32;
-            self.speed += 0.5_f64; // Slower increase - now only 0.5 per upgrade instead of 1
+    pub fn upgrade(&mut self, stat: Stat, wallet: &mut Wallet, modifiers: &Modifiers) -> bool {
+        // Pay for the upgrade if the team can afford it
+        if !wallet.spend_lengths(self.upgrade_price(stat, modifiers)) {
+            return false;
+        }
+
+        match stat {
+            Stat::Speed => {
+                self.speed += 0.5_f64; // Slower increase - now only 0.5 per upgrade instead of 1
 
-            // Calculate new upgrade cost with the multiplier
-            self.upgrade_cost = (self.upgrade_cost as f64 * self.upgrade_multiplier) as usize;
+                // Calculate new upgrade cost with the multiplier
+                self.upgrade_cost = (self.upgrade_cost as f64 * self.upgrade_multiplier) as usize;
+
+                // Slightly increase the multiplier each time
+                self.upgrade_multiplier += 0.02_f64;
+            }
+            _ => {
+                *self.stat_levels.entry(stat).or_insert(0_u32) += 1_u32;
+            }
+        }
+        self.record(format!(
+            "{} upgraded to {}",
+            stat.name(),
+            self.display_stat(stat)
+        ));
+        true
+    }
+
+    /// Gets the speed the swimmer actually swims at after global modifiers
+    ///
+    /// # Arguments
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    ///
+    /// # Returns
+    /// The effective speed
+    pub fn effective_speed(&self, modifiers: &Modifiers) -> f64 {
+        self.speed_swimming(self.current_stroke(), self.direction, modifiers)
+    }
 
-            // Slightly increase the multiplier each time
-            self.upgrade_multiplier += 0.02_f64;
+    /// Gets the speed the swimmer would swim at in the given stroke and direction
+    ///
+    /// # Arguments
+    /// * `stroke` - The stroke swum on the length
+    /// * `direction` - `true` for a length away from the left wall, `false` for one back to it
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    ///
+    /// # Returns
+    /// The effective speed
+    fn speed_swimming(&self, stroke: Stroke, direction: bool, modifiers: &Modifiers) -> f64 {
+        let perk_bonus: f64 = self
+            .perks
+            .iter()
+            .map(|perk: &Perk| match perk.effect {
+                PerkEffect::Speed { bonus } => bonus,
+                PerkEffect::OutwardSpeed { bonus } if direction => bonus,
+                PerkEffect::ReturnSpeed { bonus } if !direction => bonus,
+                _ => 0_f64,
+            })
+            .sum();
+
+        self.speed
+            * (1_f64 + perk_bonus)
+            * stroke.speed_multiplier()
+            * self.energy_factor()
+            * modifiers.speed_multiplier
+    }
+
+    /// Gets the multiplier perks apply to the swimmer's upgrade and retraining prices
+    fn perk_discount(&self) -> f64 {
+        self.perks
+            .iter()
+            .map(|perk: &Perk| match perk.effect {
+                PerkEffect::UpgradeDiscount { discount } => 1_f64 - discount,
+                _ => 1_f64,
+            })
+            .product()
+    }
+
+    /// Gets what the next upgrade of a stat costs after global modifiers
+    ///
+    /// # Arguments
+    /// * `stat` - The stat to upgrade
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    ///
+    /// # Returns
+    /// The price in lengths
+    pub fn upgrade_price(&self, stat: Stat, modifiers: &Modifiers) -> u64 {
+        // Speed keeps its own growing multiplier, the other stats grow at a fixed rate
+        let cost: f64 = match stat {
+            Stat::Speed => self.upgrade_cost as f64,
+            _ => stat.base_cost() * stat.cost_growth().powi(self.stat_level(stat) as i32),
+        };
+        (cost * self.perk_discount() * modifiers.upgrade_cost_multiplier).ceil() as u64
+    }
+
+    /// Gets what retraining the swimmer into another stroke costs after global modifiers
+    ///
+    /// # Arguments
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    ///
+    /// # Returns
+    /// The price in lengths
+    pub fn retrain_price(&self, modifiers: &Modifiers) -> u64 {
+        let cost: f64 = BASE_RETRAIN_COST * 2_f64.powi(self.retrains as i32);
+        (cost * self.perk_discount() * modifiers.upgrade_cost_multiplier).ceil() as u64
+    }
 
-            return true;
+    /// Attempts to retrain the swimmer into another stroke using lengths from the team wallet
+    ///
+    /// # Arguments
+    /// * `stroke` - The stroke to switch to
+    /// * `wallet` - The team wallet to pay from
+    /// * `modifiers` - Global modifiers from permanent upgrades
+    ///
+    /// # Returns
+    /// `true` if successful, `false` if not enough lengths
+    pub fn retrain(&mut self, stroke: Stroke, wallet: &mut Wallet, modifiers: &Modifiers) -> bool {
+        if !wallet.spend_lengths(self.retrain_price(modifiers)) {
+            return false;
         }
-        false
+        self.stroke = stroke;
+        self.retrains += 1_u32;
+        self.record(format!("Retrained to {}", stroke.name()));
+        true
     }
 
     /// Gets the display speed (rounded to one decimal place)
     pub fn display_speed(&self) -> String {
         format!("{:.1}", self.speed)
     }
+
+    /// Gets the display value of a stat
+    ///
+    /// # Arguments
+    /// * `stat` - The stat to show
+    ///
+    /// # Returns
+    /// The value formatted for the UI, with times in seconds
+    pub fn display_stat(&self, stat: Stat) -> String {
+        let tick_secs: f64 = FRAME_DURATION_MS as f64 / 1000_f64;
+        match stat {
+            Stat::Speed => self.display_speed(),
+            Stat::Stamina => format!("{:.0}%", self.stamina() * 100_f64),
+            Stat::Turns => format!("{:.2}s", self.turn_ticks() * tick_secs),
+            Stat::Reaction => format!("{:.2}s", self.reaction_ticks() * tick_secs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads a swimmer saved before the team wallet, holding its own spending balance
+    fn legacy_swimmer(lengths: i64) -> Swimmer {
+        let json: String = format!(
+            r#"{{"name":"Old Timer","progress":0.0,"speed":1.0,"lengths":{},"upgrade_cost":10,"position":0.0,"direction":true,"upgrade_multiplier":1.2}}"#,
+            lengths
+        );
+        serde_json::from_str::<Swimmer>(&json).unwrap()
+    }
+
+    /// Hires a medley swimmer with a fixed seed
+    fn medley_swimmer() -> Swimmer {
+        let mut swimmer: Swimmer =
+            Swimmer::new("Medley Swimmer", 3_f64, &mut GameRng::seeded(1_u64));
+        swimmer.stroke = Stroke::Medley;
+        swimmer
+    }
+
+    #[test]
+    fn medley_swims_each_length_in_the_stroke_of_its_leg() {
+        let modifiers: Modifiers = Modifiers::default();
+        let mut swimmer: Swimmer = medley_swimmer();
+        let mut legs: Vec<Stroke> = Vec::new();
+        for _ in 0_u32..20_000_u32 {
+            if legs.last() != Some(&swimmer.current_stroke()) {
+                legs.push(swimmer.current_stroke());
+            }
+            swimmer.swim(&modifiers);
+        }
+
+        let order: [Stroke; 5] = [
+            Stroke::Butterfly,
+            Stroke::Backstroke,
+            Stroke::Breaststroke,
+            Stroke::Freestyle,
+            Stroke::Butterfly,
+        ];
+        assert!(legs[..5] == order);
+    }
+
+    #[test]
+    fn medley_speed_follows_the_leg() {
+        let modifiers: Modifiers = Modifiers::default();
+        let swimmer: Swimmer = medley_swimmer();
+        let butterfly: f64 = swimmer.speed_swimming(Stroke::Butterfly, true, &modifiers);
+        assert_eq!(swimmer.effective_speed(&modifiers), butterfly);
+        assert!(butterfly > swimmer.speed_swimming(Stroke::Breaststroke, true, &modifiers));
+    }
+
+    #[test]
+    fn batched_medley_matches_swimming_tick_by_tick() {
+        let modifiers: Modifiers = Modifiers::default();
+        let mut stepped: Swimmer = medley_swimmer();
+        let mut batched: Swimmer = medley_swimmer();
+
+        let stepped_lengths: u64 = (0_u32..5_000_u32).map(|_| stepped.swim(&modifiers)).sum();
+        let batched_lengths: u64 = batched.swim_ticks(5_000_u64, &modifiers);
+
+        assert!(stepped_lengths > 8_u64);
+        assert!(stepped_lengths.abs_diff(batched_lengths) <= 1_u64);
+        assert!((stepped.progress - batched.progress).abs() < LANE_LENGTH);
+    }
+
+    #[test]
+    fn legacy_balance_is_taken_once() {
+        let mut swimmer: Swimmer = legacy_swimmer(40_i64);
+        assert_eq!(swimmer.take_legacy_lengths(), 40_u64);
+        assert_eq!(swimmer.lifetime_lengths, 40_u64);
+        assert_eq!(swimmer.take_legacy_lengths(), 0_u64);
+    }
+
+    #[test]
+    fn overspent_legacy_balance_is_clamped_to_zero() {
+        let mut swimmer: Swimmer = legacy_swimmer(-5_i64);
+        assert_eq!(swimmer.take_legacy_lengths(), 0_u64);
+        assert_eq!(swimmer.lifetime_lengths, 0_u64);
+    }
+
+    #[test]
+    fn legacy_balance_is_not_saved_again() {
+        let mut swimmer: Swimmer = legacy_swimmer(40_i64);
+        swimmer.take_legacy_lengths();
+        let json: String = serde_json::to_string(&swimmer).unwrap();
+        assert!(!json.contains("\"lengths\""));
+        assert!(json.contains("\"lifetime_lengths\":40"));
+    }
 }
@@ -0,0 +1,126 @@
+use crate::ui::display::{display_ui, Renderer, Viewport};
+use crate::ui::input::KeyReader;
+use crate::ui::messages::{show_goodbye_message, show_offline_summary, show_restore_menu};
+use crate::ui::terminal::TerminalSession;
+use miette::Result;
+use std::thread;
+use std::time::{Duration, Instant};
+use swimming_idle::game::{Game, FRAME_DURATION_MS};
+use swimming_idle::save::Backup;
+
+use crossterm::event::KeyCode;
+
+/// Runs the main game loop
+///
+/// # Arguments
+/// * `game` - The game to play
+///
+/// # Returns
+/// A Result indicating success or failure
+pub fn run(game: &mut Game) -> Result<()> {
+    // Take over the terminal, it is restored when the session is dropped
+    let session: TerminalSession = TerminalSession::start()?;
+
+    // Show what happened while the game was closed before starting the loop
+    if let Some(offline_progress) = game.take_offline_progress() {
+        show_offline_summary(&offline_progress)?;
+        session.wait_for_key()?;
+    }
+
+    // Display the initial UI before entering the game loop
+    let mut renderer: Renderer = Renderer::default();
+    let mut viewport: Viewport = Viewport::default();
+    display_ui(&mut renderer, &mut viewport, game)?;
+
+    let mut quit: bool = false;
+    let mut key_reader: KeyReader = KeyReader::default();
+    // Use a fixed time step for smoother animation
+    let frame_duration: Duration = Duration::from_millis(FRAME_DURATION_MS); // ~30 FPS
+    let mut last_update: Instant = Instant::now();
+
+    // Render at the simulation rate, only changed cells reach the terminal
+    let render_duration: Duration = frame_duration;
+    let mut last_render: Instant = Instant::now();
+
+    // Periodically save so a crash never loses more than one interval of progress
+    let autosave_duration: Duration = Duration::from_secs(game.config().autosave_interval_secs);
+    let mut last_autosave: Instant = Instant::now();
+
+    // A signal or Ctrl+C ends the game like quitting, minus the goodbye screen
+    while !quit && !session.interrupted() {
+        let now: Instant = Instant::now();
+        let elapsed: Duration = now.duration_since(last_update);
+
+        // Only update the game state at fixed intervals, the game keeps any leftover time
+        if elapsed >= frame_duration {
+            game.tick(elapsed);
+            last_update = now;
+        }
+
+        // Only render the UI at fixed intervals
+        if now.duration_since(last_render) >= render_duration {
+            display_ui(&mut renderer, &mut viewport, game)?;
+            last_render = now;
+        }
+
+        // Autosave at fixed intervals, an interval of 0 disables it
+        if !autosave_duration.is_zero() && now.duration_since(last_autosave) >= autosave_duration {
+            game.save()?;
+            last_autosave = now;
+        }
+
+        // Handle user input with a shorter timeout to be responsive
+        if let Some(key) = session.poll_key(Duration::from_millis(10_u64))? {
+            if let Some(action) = key_reader.read(game.keymap(), key) {
+                game.set_page_size(viewport.page_size());
+                quit = game.apply(action);
+            }
+        } else {
+            thread::sleep(Duration::from_millis(1_u64));
+        }
+    }
+
+    // Save progress before leaving
+    game.save()?;
+
+    if !session.interrupted() {
+        // Show goodbye message
+        show_goodbye_message()?;
+
+        // Wait for final keypress
+        session.wait_for_key()?;
+    }
+
+    Ok(())
+}
+
+/// Shows the restore menu until the player picks a backup or quits
+///
+/// # Arguments
+/// * `backups` - The backups available to restore
+///
+/// # Returns
+/// A Result containing the index of the chosen backup, or `None` if the player quit
+pub fn choose_backup(backups: &[Backup]) -> Result<Option<usize>> {
+    let session: TerminalSession = TerminalSession::start()?;
+
+    let mut selected_index: usize = 0_usize;
+    loop {
+        show_restore_menu(backups, selected_index)?;
+
+        let Some(key) = session.wait_for_key()? else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Up if selected_index > 0_usize => {
+                selected_index -= 1_usize;
+            }
+            KeyCode::Down if selected_index < backups.len() - 1_usize => {
+                selected_index += 1_usize;
+            }
+            KeyCode::Enter => return Ok(Some(selected_index)),
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use swimming_idle::achievements::{Achievement, Achievements};
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Width of the progress bar of a locked achievement, in cells
+const PROGRESS_BAR_WIDTH: usize = 10;
+
+/// Column the achievement entries start at
+const LEFT_MARGIN: u16 = 4;
+
+/// Renders the achievements screen: every achievement with its bonus and how close the team is to it
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `achievements` - The achievements unlocked so far and the totals behind them
+/// * `catalogue` - Every achievement that can be unlocked
+/// * `selected_index` - Index of the highlighted achievement
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+/// * `bottom_row` - First row kept free for the footer
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_achievements(
+    frame: &mut FrameBuffer,
+    achievements: &Achievements,
+    catalogue: &[Achievement],
+    selected_index: usize,
+    terminal_width: usize,
+    header_height: u16,
+    bottom_row: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+
+    let title: String = format!(
+        "🏅 Achievements | {} / {} unlocked 🏅",
+        achievements.unlocked_count(),
+        catalogue.len()
+    );
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Yellow).bold());
+    current_row += 2;
+
+    // Scroll the list so the highlighted achievement is always on screen, two rows each
+    let rows: usize = (bottom_row.saturating_sub(current_row + 1) as usize / 2).max(1);
+    let first: usize = selected_index.saturating_sub(rows - 1);
+
+    for (i, achievement) in catalogue.iter().enumerate().skip(first).take(rows) {
+        let is_selected: bool = i == selected_index;
+        let unlocked: bool = achievements.unlocked_at(achievement).is_some();
+
+        let (mut title_style, mut detail_style): (Style, Style) = if unlocked {
+            (Style::default().fg(Color::Yellow).bold(), Style::default().fg(Color::Grey))
+        } else {
+            (Style::default().fg(Color::Grey), Style::default().fg(Color::DarkGrey))
+        };
+        if is_selected {
+            title_style = Style::default().fg(Color::White).bg(Color::DarkGrey).bold();
+            detail_style = Style::default().fg(Color::White).bg(Color::DarkGrey);
+            let box_width: usize = terminal_width.saturating_sub(6);
+            frame.set_string(3, current_row, &" ".repeat(box_width), title_style);
+            frame.set_string(3, current_row + 1, &" ".repeat(box_width), detail_style);
+        }
+
+        let title: String = format!(
+            "{} {} {} - {}",
+            if is_selected { "➤" } else { " " },
+            if unlocked { "🏅" } else { "🔒" },
+            achievement.name,
+            achievement.description
+        );
+        frame.set_string(LEFT_MARGIN, current_row, &title, title_style);
+
+        // Locked achievements show how close the team is instead
+        let status: String = if unlocked {
+            "Unlocked".to_string()
+        } else {
+            let (current, target): (u64, u64) = achievements.progress(achievement);
+            let current: u64 = current.min(target);
+            let filled: usize = (current * PROGRESS_BAR_WIDTH as u64 / target.max(1)) as usize;
+            format!(
+                "[{}{}] {} / {}",
+                "█".repeat(filled),
+                "░".repeat(PROGRESS_BAR_WIDTH - filled),
+                current,
+                target
+            )
+        };
+        let detail: String = format!("Bonus: {} | {}", achievement.bonus.description(), status);
+        frame.set_string(LEFT_MARGIN + 7, current_row + 1, &detail, detail_style);
+        current_row += 2;
+    }
+
+    current_row
+}
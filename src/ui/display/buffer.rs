@@ -0,0 +1,372 @@
+use miette::{IntoDiagnostic, Result};
+use std::io::Write;
+use unicode_width::UnicodeWidthChar;
+
+use crossterm::{
+    cursor, queue,
+    style::{Attribute, Color, Print, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+
+/// Marks a cell covered by the right half of a wide character
+const CONTINUATION: char = '\0';
+
+/// Colors and attributes of a single cell
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Color,
+    pub bg: Color,
+    pub bold: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fg: Color::Reset,
+            bg: Color::Reset,
+            bold: false,
+        }
+    }
+}
+
+impl Style {
+    /// Returns the style with the given foreground color
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = color;
+        self
+    }
+
+    /// Returns the style with the given background color
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = color;
+        self
+    }
+
+    /// Returns the style in bold
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+}
+
+/// A single character position on screen
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    symbol: char,
+    style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+/// An in-memory grid of cells that a frame is drawn into before reaching the terminal
+pub struct FrameBuffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+}
+
+impl FrameBuffer {
+    /// Creates a blank frame of the given size
+    ///
+    /// # Arguments
+    /// * `width` - Number of columns
+    /// * `height` - Number of rows
+    ///
+    /// # Returns
+    /// A new FrameBuffer
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+
+    /// Writes a string starting at the given position, clipping anything off-screen
+    ///
+    /// # Arguments
+    /// * `x` - Column of the first character
+    /// * `y` - Row to write on
+    /// * `text` - The text to write
+    /// * `style` - Colors and attributes of the text
+    pub fn set_string(&mut self, x: u16, y: u16, text: &str, style: Style) {
+        if y >= self.height {
+            return;
+        }
+
+        let mut column: u16 = x;
+        for symbol in text.chars() {
+            let symbol_width: u16 = symbol.width().unwrap_or(0_usize) as u16;
+            if symbol_width == 0_u16 {
+                continue;
+            }
+            if column + symbol_width > self.width {
+                break;
+            }
+
+            self.set_cell(column, y, symbol, style);
+            if symbol_width == 2_u16 {
+                self.set_cell(column + 1_u16, y, CONTINUATION, style);
+            }
+            column += symbol_width;
+        }
+    }
+
+    /// Fills a whole row with blank cells of the given style
+    ///
+    /// # Arguments
+    /// * `y` - Row to fill
+    /// * `style` - Style of the blank cells
+    pub fn fill_row(&mut self, y: u16, style: Style) {
+        self.set_string(0, y, &" ".repeat(self.width as usize), style);
+    }
+
+    /// Sets one cell, blanking the other half of any wide character it breaks up
+    fn set_cell(&mut self, x: u16, y: u16, symbol: char, style: Style) {
+        let index: usize = self.index(x, y);
+
+        // Overwriting the right half of a wide character leaves its left half dangling
+        if self.cells[index].symbol == CONTINUATION && symbol != CONTINUATION && x > 0_u16 {
+            self.cells[index - 1_usize].symbol = ' ';
+        }
+        // Overwriting the left half of a wide character leaves its right half dangling
+        if x + 1_u16 < self.width
+            && self.cells[index + 1_usize].symbol == CONTINUATION
+            && self.cells[index].symbol.width() == Some(2_usize)
+        {
+            self.cells[index + 1_usize].symbol = ' ';
+        }
+
+        self.cells[index] = Cell { symbol, style };
+    }
+
+    /// Converts a position to an index into the cell grid
+    fn index(&self, x: u16, y: u16) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+/// Keeps the last frame sent to the terminal so only changed cells are redrawn
+#[derive(Default)]
+pub struct Renderer {
+    previous: Option<FrameBuffer>,
+}
+
+impl Renderer {
+    /// Sends the cells that changed since the last frame to the terminal
+    ///
+    /// # Arguments
+    /// * `stdout` - The output stream to write to
+    /// * `frame` - The newly drawn frame
+    ///
+    /// # Returns
+    /// A Result indicating success or an error
+    pub fn draw<W: Write>(&mut self, stdout: &mut W, frame: FrameBuffer) -> Result<()> {
+        // Repaint everything on the first frame or after the terminal was resized
+        let previous: Option<&FrameBuffer> = self
+            .previous
+            .as_ref()
+            .filter(|previous| previous.width == frame.width && previous.height == frame.height);
+        if previous.is_none() {
+            queue!(
+                stdout,
+                SetAttribute(Attribute::Reset),
+                Clear(ClearType::All)
+            )
+            .into_diagnostic()?;
+        }
+
+        let changed: Vec<bool> = Self::changed_cells(previous, &frame);
+
+        // Only move the cursor or change styles when the next cell needs it
+        let mut cursor_position: Option<(u16, u16)> = None;
+        let mut current_style: Option<Style> = None;
+        for y in 0..frame.height {
+            for x in 0..frame.width {
+                let index: usize = frame.index(x, y);
+                let cell: Cell = frame.cells[index];
+                if !changed[index] || cell.symbol == CONTINUATION {
+                    continue;
+                }
+
+                if cursor_position != Some((x, y)) {
+                    queue!(stdout, cursor::MoveTo(x, y)).into_diagnostic()?;
+                }
+                if current_style != Some(cell.style) {
+                    queue!(
+                        stdout,
+                        SetAttribute(Attribute::Reset),
+                        SetForegroundColor(cell.style.fg),
+                        SetBackgroundColor(cell.style.bg),
+                        SetAttribute(if cell.style.bold {
+                            Attribute::Bold
+                        } else {
+                            Attribute::NormalIntensity
+                        })
+                    )
+                    .into_diagnostic()?;
+                    current_style = Some(cell.style);
+                }
+
+                queue!(stdout, Print(cell.symbol)).into_diagnostic()?;
+                let symbol_width: u16 = cell.symbol.width().unwrap_or(1_usize) as u16;
+                cursor_position = Some((x + symbol_width, y));
+            }
+        }
+
+        queue!(stdout, SetAttribute(Attribute::Reset)).into_diagnostic()?;
+        stdout.flush().into_diagnostic()?;
+
+        self.previous = Some(frame);
+        Ok(())
+    }
+
+    /// Works out which cells need to be sent to the terminal
+    ///
+    /// # Arguments
+    /// * `previous` - The last frame drawn, or `None` to redraw everything
+    /// * `frame` - The newly drawn frame
+    ///
+    /// # Returns
+    /// One flag per cell, `true` if the cell must be redrawn
+    fn changed_cells(previous: Option<&FrameBuffer>, frame: &FrameBuffer) -> Vec<bool> {
+        let Some(previous) = previous else {
+            return vec![true; frame.cells.len()];
+        };
+
+        let mut changed: Vec<bool> = frame
+            .cells
+            .iter()
+            .zip(previous.cells.iter())
+            .map(|(cell, previous_cell)| cell != previous_cell)
+            .collect();
+
+        // A wide character spans two cells, so a change to either half redraws the lead cell
+        for index in 1_usize..changed.len() {
+            let touches_wide: bool = frame.cells[index].symbol == CONTINUATION
+                || previous.cells[index].symbol == CONTINUATION;
+            if changed[index] && touches_wide {
+                changed[index - 1_usize] = true;
+            }
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads a row of a frame back as text, leaving out the right halves of wide characters
+    fn row(frame: &FrameBuffer, y: u16) -> String {
+        (0..frame.width)
+            .map(|x: u16| frame.cells[frame.index(x, y)].symbol)
+            .filter(|symbol: &char| *symbol != CONTINUATION)
+            .collect()
+    }
+
+    /// Draws a frame and returns what was sent to the terminal
+    fn draw(renderer: &mut Renderer, frame: FrameBuffer) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        renderer.draw(&mut output, frame).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Builds a frame with a line of text on its first row
+    fn frame_with(text: &str) -> FrameBuffer {
+        let mut frame: FrameBuffer = FrameBuffer::new(8_u16, 2_u16);
+        frame.set_string(0_u16, 0_u16, text, Style::default());
+        frame
+    }
+
+    #[test]
+    fn text_is_clipped_at_the_edge() {
+        let mut frame: FrameBuffer = FrameBuffer::new(4_u16, 1_u16);
+
+        frame.set_string(1_u16, 0_u16, "abcdef", Style::default());
+        frame.set_string(0_u16, 5_u16, "off screen", Style::default());
+
+        assert_eq!(row(&frame, 0_u16), " abc");
+    }
+
+    #[test]
+    fn wide_characters_take_two_cells() {
+        let mut frame: FrameBuffer = FrameBuffer::new(4_u16, 1_u16);
+
+        frame.set_string(0_u16, 0_u16, "🏊ab", Style::default());
+        assert_eq!(row(&frame, 0_u16), "🏊ab");
+
+        // A wide character that would stick out past the edge is left out
+        frame.set_string(3_u16, 0_u16, "🏊", Style::default());
+        assert_eq!(row(&frame, 0_u16), "🏊ab");
+    }
+
+    #[test]
+    fn breaking_up_a_wide_character_blanks_its_other_half() {
+        let mut frame: FrameBuffer = FrameBuffer::new(4_u16, 1_u16);
+
+        frame.set_string(0_u16, 0_u16, "🏊🏊", Style::default());
+        frame.set_string(1_u16, 0_u16, "x", Style::default());
+        assert_eq!(row(&frame, 0_u16), " x🏊");
+
+        frame.set_string(2_u16, 0_u16, "y", Style::default());
+        assert_eq!(row(&frame, 0_u16), " xy ");
+    }
+
+    #[test]
+    fn first_frame_is_drawn_in_full() {
+        let mut renderer: Renderer = Renderer::default();
+
+        let output: String = draw(&mut renderer, frame_with("ab"));
+
+        assert!(output.contains("\x1b[2J"));
+        assert!(output.contains('a') && output.contains('b'));
+    }
+
+    #[test]
+    fn only_changed_cells_are_sent() {
+        let mut renderer: Renderer = Renderer::default();
+        draw(&mut renderer, frame_with("abc"));
+
+        assert!(!draw(&mut renderer, frame_with("abc")).contains('a'));
+        let output: String = draw(&mut renderer, frame_with("abz"));
+        assert!(output.contains('z'));
+        assert!(!output.contains('a') && !output.contains('b'));
+        assert!(!output.contains("\x1b[2J"));
+    }
+
+    #[test]
+    fn resizing_redraws_everything() {
+        let mut renderer: Renderer = Renderer::default();
+        draw(&mut renderer, frame_with("abc"));
+
+        let mut frame: FrameBuffer = FrameBuffer::new(9_u16, 2_u16);
+        frame.set_string(0_u16, 0_u16, "abc", Style::default());
+
+        let output: String = draw(&mut renderer, frame);
+        assert!(output.contains("\x1b[2J") && output.contains('a'));
+    }
+
+    #[test]
+    fn change_to_either_half_of_a_wide_character_redraws_it() {
+        let previous: FrameBuffer = frame_with("🏊a");
+
+        let changed: Vec<bool> = Renderer::changed_cells(Some(&previous), &frame_with("🏊b"));
+        assert_eq!(&changed[..3], &[false, false, true]);
+
+        // Replacing the right half leaves the lead cell to be redrawn as a blank
+        let changed: Vec<bool> = Renderer::changed_cells(Some(&previous), &frame_with(" xa"));
+        assert_eq!(&changed[..3], &[true, true, false]);
+        let mut renderer: Renderer = Renderer::default();
+        draw(&mut renderer, previous);
+        assert!(draw(&mut renderer, frame_with(" xa")).contains(" x"));
+    }
+}
@@ -0,0 +1,182 @@
+use swimming_idle::game::Game;
+use swimming_idle::perks::Perk;
+use swimming_idle::swimmer::{Stat, Swimmer, UpgradeRecord};
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::utils::{center_padding, format_duration, sparkline, text_width};
+use swimming_idle::utils::unix_timestamp;
+
+use crossterm::style::Color;
+use std::time::Duration;
+
+/// Width of the XP progress bar, in cells
+const XP_BAR_WIDTH: usize = 30;
+
+/// Column the detail lines start at
+const LEFT_MARGIN: u16 = 3;
+
+/// Renders the detail screen of the selected swimmer
+///
+/// Shows every stat, their pace and a sparkline of their speed, their perks
+/// and any perk offer waiting to be picked, then as much of their upgrade
+/// history as fits above the footer.
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `game` - The game state, for the selected swimmer and global modifiers
+/// * `selected_index` - Index of the highlighted perk on offer
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+/// * `bottom_row` - First row kept free for the footer
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_details(
+    frame: &mut FrameBuffer,
+    game: &Game,
+    selected_index: usize,
+    terminal_width: usize,
+    header_height: u16,
+    bottom_row: u16
+) -> u16 {
+    let swimmer: &Swimmer = &game.swimmers()[game.selected_index()];
+    let mut current_row: u16 = header_height + 1;
+
+    // Name, level and progress towards the next one
+    let level: u32 = swimmer.level();
+    let level_start: f64 = Swimmer::xp_for_level(level);
+    let level_end: f64 = Swimmer::xp_for_level(level + 1);
+    let fraction: f64 = ((swimmer.xp() - level_start) / (level_end - level_start)).clamp(0_f64, 1_f64);
+    let filled: usize = (fraction * XP_BAR_WIDTH as f64) as usize;
+    let title: String = format!(
+        "{} | ⭐ Level {} | XP: {:.0} / {:.0} [{}{}]",
+        swimmer.name,
+        level,
+        swimmer.xp(),
+        level_end,
+        "█".repeat(filled),
+        "░".repeat(XP_BAR_WIDTH - filled)
+    );
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Yellow).bold());
+    current_row += 2;
+
+    // Every stat
+    let value_style: Style = Style::default().fg(Color::Grey);
+    let stats: String = format!(
+        "Stroke: {} | Speed: {} ({:.2} now) | Stamina: {} | Turns: {} | Start: {}",
+        swimmer.stroke.name(),
+        swimmer.display_stat(Stat::Speed),
+        swimmer.effective_speed(&game.modifiers()),
+        swimmer.display_stat(Stat::Stamina),
+        swimmer.display_stat(Stat::Turns),
+        swimmer.display_stat(Stat::Reaction)
+    );
+    frame.set_string(LEFT_MARGIN, current_row, &stats, value_style);
+    current_row += 1;
+
+    let totals: String = format!(
+        "Energy: {:.0}%{} | Distance: {:.0} | Lifetime: {} lengths | Pace: {:.1} lengths/min",
+        swimmer.energy() * 100_f64,
+        if swimmer.resting { " (resting)" } else { "" },
+        swimmer.progress,
+        swimmer.lifetime_lengths,
+        swimmer.lengths_per_minute()
+    );
+    frame.set_string(LEFT_MARGIN, current_row, &totals, value_style);
+    current_row += 1;
+
+    let medals: String = format!(
+        "Medals: 🥇 {} | 🥈 {} | 🥉 {}",
+        swimmer.medals.gold,
+        swimmer.medals.silver,
+        swimmer.medals.bronze
+    );
+    frame.set_string(LEFT_MARGIN, current_row, &medals, value_style);
+    current_row += 1;
+
+    // Speed over time, newest on the right
+    let samples = swimmer.speed_samples();
+    let speed_line: String = if samples.is_empty() {
+        "Speed over time: collecting...".to_string()
+    } else {
+        format!("Speed over time: {}", sparkline(samples.iter()))
+    };
+    frame.set_string(LEFT_MARGIN, current_row, &speed_line, Style::default().fg(Color::Cyan));
+    current_row += 2;
+
+    // Perks picked so far
+    frame.set_string(LEFT_MARGIN, current_row, "Perks:", Style::default().fg(Color::Cyan).bold());
+    current_row += 1;
+    if swimmer.perks().is_empty() {
+        frame.set_string(LEFT_MARGIN + 2, current_row, "None yet, level up to pick one", Style::default().fg(Color::DarkGrey));
+        current_row += 1;
+    }
+    for perk in swimmer.perks() {
+        let line: String = format!("• {}: {}", perk.name, perk.description);
+        frame.set_string(LEFT_MARGIN + 2, current_row, &line, value_style);
+        current_row += 1;
+    }
+    current_row += 1;
+
+    // Perks on offer for a level-up that has not been claimed yet
+    let offer: &[Perk] = swimmer.perk_offer();
+    if !offer.is_empty() {
+        current_row = render_perk_offer(frame, swimmer, offer, selected_index, terminal_width, current_row);
+    }
+
+    // Upgrade history, newest first, cut off where the footer starts
+    let history: &[UpgradeRecord] = swimmer.history();
+    if current_row + 1 >= bottom_row {
+        return current_row;
+    }
+    frame.set_string(LEFT_MARGIN, current_row, "History:", Style::default().fg(Color::Cyan).bold());
+    current_row += 1;
+    if history.is_empty() {
+        frame.set_string(LEFT_MARGIN + 2, current_row, "Nothing yet", Style::default().fg(Color::DarkGrey));
+        current_row += 1;
+    }
+    let now: u64 = unix_timestamp();
+    for record in history.iter().rev() {
+        if current_row >= bottom_row {
+            break;
+        }
+        let ago: String = format_duration(Duration::from_secs(now.saturating_sub(record.at)));
+        let line: String = format!("• {} ago: {}", ago, record.description);
+        frame.set_string(LEFT_MARGIN + 2, current_row, &line, value_style);
+        current_row += 1;
+    }
+
+    current_row
+}
+
+/// Renders the perks on offer for a level-up, highlighted like the selected swimmer
+fn render_perk_offer(
+    frame: &mut FrameBuffer,
+    swimmer: &Swimmer,
+    offer: &[Perk],
+    selected_index: usize,
+    terminal_width: usize,
+    start_row: u16
+) -> u16 {
+    let mut current_row: u16 = start_row;
+
+    let title: String = format!("🎉 Level-up! Pick a perk ({} waiting)", swimmer.unclaimed_perks());
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Green).bold());
+    current_row += 1;
+
+    for (i, perk) in offer.iter().enumerate() {
+        let is_selected: bool = i == selected_index;
+        let line: String = format!(
+            "{} {} | {}",
+            if is_selected { "➤" } else { " " },
+            perk.name,
+            perk.description
+        );
+        render_entry(frame, &line, is_selected, entry_style(true), terminal_width, current_row);
+        current_row += 1;
+    }
+
+    current_row + 1
+}
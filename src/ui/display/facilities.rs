@@ -0,0 +1,81 @@
+use swimming_idle::pool::{Facility, Pool};
+use swimming_idle::wallet::Wallet;
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Renders the facilities screen with every pool upgrade and the choice of pool length
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `pool` - The team's home pool
+/// * `wallet` - The team wallet, to grey out upgrades the team cannot afford
+/// * `swimmers` - Number of swimmers, to show how many lanes are taken
+/// * `selected_index` - Index of the highlighted entry
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_facilities(
+    frame: &mut FrameBuffer,
+    pool: &Pool,
+    wallet: &Wallet,
+    swimmers: usize,
+    selected_index: usize,
+    terminal_width: usize,
+    header_height: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+
+    let title: String = format!(
+        "🔧 {} Pool | Lanes: {} / {} | Prize money: {} 🔧",
+        pool.length.name(),
+        swimmers,
+        pool.lanes(),
+        wallet.prize_money
+    );
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Magenta).bold());
+    current_row += 2;
+
+    for (i, facility) in Facility::ALL.iter().enumerate() {
+        let price: u64 = pool.price(*facility);
+        let line: String = format!(
+            "{} {} (Level {}) | {} | Next: {} prize money",
+            if i == selected_index { "➤" } else { " " },
+            facility.name(),
+            pool.level(*facility),
+            facility.description(),
+            price
+        );
+        render_entry(frame, &line, i == selected_index, entry_style(wallet.prize_money >= price), terminal_width, current_row);
+        current_row += 2;
+    }
+
+    // The pool length is a switch rather than a level, so it is listed last
+    let is_selected: bool = selected_index == Facility::ALL.len();
+    let price: u64 = pool.switch_price();
+    let cost: String = if price > 0 {
+        format!("Build for {} prize money", price)
+    } else {
+        "Free".to_string()
+    };
+    let line: String = format!(
+        "{} 🔄 Switch to the {} pool | {}",
+        if is_selected { "➤" } else { " " },
+        pool.length.other().name(),
+        cost
+    );
+    render_entry(frame, &line, is_selected, entry_style(wallet.prize_money >= price), terminal_width, current_row);
+    current_row += 1;
+
+    let note: &str = "A 50 m length pays double and tires twice as much, but swimmers turn half as often";
+    let note_padding: usize = center_padding(text_width(note), terminal_width);
+    frame.set_string(note_padding as u16, current_row, note, Style::default().fg(Color::DarkGrey));
+    current_row += 1;
+
+    current_row
+}
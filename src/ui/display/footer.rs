@@ -1,35 +1,190 @@
-use miette::{IntoDiagnostic, Result};
-use std::io::Write;
+use swimming_idle::action::Action;
+use swimming_idle::game::{Game, Screen};
+use swimming_idle::keymap::Keymap;
+use swimming_idle::notifications::NotificationKind;
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::utils::{center_padding, text_width};
 
-use crossterm::{
-    cursor, execute,
-    style::{Color, Print, ResetColor, SetForegroundColor, SetBackgroundColor},
-    terminal::Clear, terminal::ClearType,
-};
+use crossterm::style::Color;
+use std::ops::Range;
+
+/// Footer rows the key hints can wrap over, the notifications start below them
+const HINT_ROWS: u16 = 2;
 
 /// Renders the footer section of the UI
 /// 
 /// # Arguments
-/// * `stdout` - The output stream to write to
+/// * `frame` - The frame buffer to draw into
 /// * `current_row` - The current row position for the footer
 /// * `terminal_width` - Width of the terminal
+/// * `game` - The game state, for the screen's key hints and the notifications
+/// * `visible` - Indices of the swimmers on screen, for the scroll indicator
+pub fn render_footer(
+    frame: &mut FrameBuffer, 
+    current_row: u16, 
+    terminal_width: usize,
+    game: &Game,
+    visible: Range<usize>
+) {
+    let total_swimmers: usize = game.swimmers().len();
+    let footer_style: Style = Style::default().fg(Color::White).bg(Color::DarkBlue);
+    frame.fill_row(current_row + 1, footer_style);
+
+    // Scroll indicator on the right when not every swimmer fits on screen
+    let mut indicator_width: usize = 0;
+    if visible.len() < total_swimmers {
+        let indicator: String = format!(
+            "{} {}–{} of {} {} ",
+            if visible.start > 0 { "▲" } else { " " },
+            visible.start + 1,
+            visible.end,
+            total_swimmers,
+            if visible.end < total_swimmers { "▼" } else { " " }
+        );
+        indicator_width = text_width(&indicator);
+        frame.set_string(
+            (terminal_width as u16).saturating_sub(indicator_width as u16),
+            current_row + 1,
+            &indicator,
+            footer_style.bold()
+        );
+    }
+
+    // Hints wrap onto the next footer row, the last row before the notifications ends in an ellipsis
+    let hints: Vec<String> = screen_hints(game.screen(), game.keymap());
+    let last_row: u16 = current_row + HINT_ROWS;
+    let mut row: u16 = current_row + 1;
+    let mut width: usize = terminal_width.saturating_sub(indicator_width);
+    let mut line: String = String::new();
+    let mut truncated: bool = false;
+    for hint in hints {
+        let candidate: String = if line.is_empty() {
+            format!(" {}", hint)
+        } else {
+            format!("{} | {}", line, hint)
+        };
+        if text_width(&candidate) <= width || line.is_empty() {
+            line = candidate;
+        } else if row < last_row {
+            // A single hint wider than the row is cut short on its own
+            if text_width(&line) > width {
+                line = with_ellipsis(&line, width);
+            }
+            frame.set_string(0, row, &line, footer_style);
+            row += 1;
+            width = terminal_width;
+            frame.fill_row(row, footer_style);
+            line = format!(" {}", hint);
+        } else {
+            truncated = true;
+            break;
+        }
+    }
+    if truncated || text_width(&line) > width {
+        line = with_ellipsis(&line, width);
+    }
+    frame.set_string(0, row, &line, footer_style);
+
+    // Stack the active notifications below the footer, newest at the bottom
+    for (i, notification) in game.notifications().iter().enumerate() {
+        let row: u16 = current_row + HINT_ROWS + 1 + i as u16;
+        let message: String = format!("  {}  ", notification.message);
+        let padding: usize = center_padding(text_width(&message), terminal_width);
+        let style: Style = match notification.kind {
+            NotificationKind::Success => Style::default().fg(Color::Green).bg(Color::DarkGreen),
+            NotificationKind::Failure => Style::default().fg(Color::Red).bg(Color::DarkRed),
+            NotificationKind::Achievement => Style::default().fg(Color::Yellow).bg(Color::DarkYellow),
+        };
+
+        frame.set_string(padding as u16, row, &message, style.bold());
+    }
+}
+
+/// Builds the key hints of a screen from the keymap in use, skipping actions with no key
+/// 
+/// # Arguments
+/// * `screen` - The screen being shown
+/// * `keymap` - The key bindings in use
 /// 
 /// # Returns
-/// A Result indicating success or an error
-pub fn render_footer<W: Write>(
-    stdout: &mut W, 
-    current_row: u16, 
-    _terminal_width: usize
-) -> Result<()> {
-    execute!(
-        stdout,
-        cursor::MoveTo(0, current_row + 1),
-        SetBackgroundColor(Color::DarkBlue),
-        Clear(ClearType::CurrentLine),
-        SetForegroundColor(Color::White),
-        Print(format!(" Lengths to be converted to points in future updates!")),
-        ResetColor
-    ).into_diagnostic()?;
-    
-    Ok(())
+/// The hints, such as `[Enter] Details`, in the order they are shown
+fn screen_hints(screen: Screen, keymap: &Keymap) -> Vec<String> {
+    let select: [Action; 2] = [Action::SelectPrev, Action::SelectNext];
+    let hints: Vec<Option<String>> = match screen {
+        Screen::Pool => vec![
+            keymap.hint(&[Action::Confirm], "Details"),
+            keymap.hint(&[Action::Convert], "Points"),
+            keymap.hint(&[Action::Shop], "Shop"),
+            keymap.hint(&[Action::Season], "Season"),
+            keymap.hint(&[Action::Meet], "Meet"),
+            keymap.hint(&[Action::League], "League"),
+            keymap.hint(&[Action::Relay], "Relay"),
+            keymap.hint(&[Action::Facilities], "Facilities"),
+            keymap.hint(&[Action::Achievements], "Achievements"),
+        ],
+        Screen::Upgrade { .. } => vec![
+            keymap.hint(&select, "Select"),
+            keymap.hint(&[Action::CyclePrev, Action::CycleNext], "Pick stroke"),
+            keymap.hint(&[Action::Confirm], "Upgrade / Retrain"),
+            keymap.hint(&[Action::Back], "Back"),
+        ],
+        Screen::Details { .. } => vec![
+            keymap.hint(&select, "Select perk"),
+            keymap.hint(&[Action::Confirm], "Pick perk"),
+            keymap.hint(&[Action::Back], "Back to pool"),
+        ],
+        Screen::Shop { .. } => vec![
+            keymap.hint(&select, "Select"),
+            keymap.hint(&[Action::Confirm], "Buy"),
+            keymap.hint(&[Action::Convert], "Convert lengths"),
+            keymap.hint(&[Action::Back], "Back"),
+        ],
+        Screen::Season { .. } => vec![
+            keymap.hint(&select, "Select"),
+            keymap.hint(&[Action::Confirm], "Buy / Start new season"),
+            keymap.hint(&[Action::Back], "Back"),
+        ],
+        Screen::Meet { .. } => vec![
+            keymap.hint(&select, "Select"),
+            keymap.hint(&[Action::Toggle], "Enter / Withdraw"),
+            keymap.hint(&[Action::Confirm], "Start race"),
+            keymap.hint(&[Action::Back], "Back"),
+        ],
+        Screen::Race => vec![
+            keymap.hint(&[Action::Confirm], "Back to pool once every swimmer has finished"),
+        ],
+        Screen::League => vec![keymap.hint(&[Action::Back], "Back to pool")],
+        Screen::Relay { .. } => vec![
+            keymap.hint(&select, "Select"),
+            keymap.hint(&[Action::Toggle], "Pick / Drop"),
+            keymap.hint(&[Action::Confirm], "Form relay"),
+            keymap.hint(&[Action::Back], "Back"),
+        ],
+        Screen::Facilities { .. } => vec![
+            keymap.hint(&select, "Select"),
+            keymap.hint(&[Action::Confirm], "Buy / Switch pool length"),
+            keymap.hint(&[Action::Back], "Back"),
+        ],
+        Screen::Achievements { .. } => vec![
+            keymap.hint(&select, "Scroll"),
+            keymap.hint(&[Action::Back], "Back to pool"),
+        ],
+    };
+    hints.into_iter().flatten().collect()
+}
+
+/// Cuts a footer line short so that it ends in an ellipsis within the given width
+/// 
+/// # Arguments
+/// * `line` - The line of hints
+/// * `width` - Columns the line has to fit in
+/// 
+/// # Returns
+/// The line with as many characters as fit before the ellipsis
+fn with_ellipsis(line: &str, width: usize) -> String {
+    let mut shortened: String = line.trim_end_matches([' ', '|']).to_string();
+    while !shortened.is_empty() && text_width(&shortened) + 2 > width {
+        shortened.pop();
+    }
+    format!("{} …", shortened.trim_end_matches([' ', '|']))
 }
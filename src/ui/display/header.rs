@@ -1,111 +1,123 @@
-use crate::swimmer::Swimmer;
-use crate::ui::utils::center_padding;
-use miette::{IntoDiagnostic, Result};
-use std::io::Write;
+use swimming_idle::action::Action;
+use swimming_idle::game::Game;
+use swimming_idle::keymap::Keymap;
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::utils::{center_padding, text_width};
 
-use crossterm::{
-    cursor, execute,
-    style::{Color, Print, ResetColor, SetForegroundColor, SetBackgroundColor, Attribute, SetAttribute},
-    terminal::Clear, terminal::ClearType,
-};
+use crossterm::style::Color;
 
 /// Renders the header section of the UI
 /// 
 /// # Arguments
-/// * `stdout` - The output stream to write to
+/// * `frame` - The frame buffer to draw into
 /// * `terminal_width` - Width of the terminal
-/// * `swimmers` - Slice of swimmer objects for stats calculations
-/// * `new_swimmer_cost` - Cost to add a new swimmer
-/// 
-/// # Returns
-/// A Result indicating success or an error
-pub fn render_header<W: Write>(
-    stdout: &mut W, 
+/// * `game` - The game state whose balances and costs are shown
+pub fn render_header(
+    frame: &mut FrameBuffer, 
     terminal_width: usize, 
-    swimmers: &[Swimmer],
-    new_swimmer_cost: usize
-) -> Result<()> {
-    // Draw header background for the entire header area
-    for y in 0..7_u16 {
-        execute!(
-            stdout,
-            cursor::MoveTo(0, y),
-            SetBackgroundColor(if y == 0 { Color::DarkBlue } else { Color::Reset }),
-            Clear(ClearType::CurrentLine)
-        ).into_diagnostic()?;
-    }
+    game: &Game
+) {
+    // Draw header background for the title line
+    let title_style: Style = Style::default().bg(Color::DarkBlue);
+    frame.fill_row(0, title_style);
     
     // Game title with enhanced styling - always on first line
     let title: &str = "🏊 Swimming Idle Game 🏊";
-    let title_padding: usize = center_padding(title.len(), terminal_width);
-    execute!(
-        stdout,
-        cursor::MoveTo(title_padding as u16, 0),
-        SetForegroundColor(Color::White),
-        SetAttribute(Attribute::Bold),
-        Print(title),
-        SetAttribute(Attribute::Reset),
-        ResetColor
-    ).into_diagnostic()?;
-    
-    // Reset background
-    execute!(stdout, ResetColor).into_diagnostic()?;
+    let title_padding: usize = center_padding(text_width(title), terminal_width);
+    frame.set_string(title_padding as u16, 0, title, title_style.fg(Color::White).bold());
     
-    // Total lengths collected - always on line 2
-    let total_lengths: i32 = swimmers.iter().map(|s| s.lengths).sum();
-    let total_swimmers: usize = swimmers.len();
+    // Team lengths available to spend - always on line 2
+    let total_swimmers: usize = game.swimmers().len();
+    let wallet = game.wallet();
     
-    execute!(
-        stdout,
-        cursor::MoveTo(1, 2),
-        SetForegroundColor(Color::Cyan),
-        Print(format!("Total Lengths: {}", total_lengths)),
-        ResetColor
-    ).into_diagnostic()?;
+    frame.set_string(
+        1,
+        2,
+        &format!("Team Lengths: {}", wallet.lengths),
+        Style::default().fg(Color::Cyan)
+    );
+
+    // Points balance and the going rate for the next one - always on line 2, centered
+    let points_text: String = format!(
+        "Points: {} (next costs {} lengths)",
+        wallet.points,
+        game.point_price()
+    );
+    let points_padding: usize = center_padding(text_width(&points_text), terminal_width);
+    frame.set_string(points_padding as u16, 2, &points_text, Style::default().fg(Color::Magenta));
     
-    // Swimmers count - always on line 2, right side
-    let swimmers_text = format!("Swimmers: {}", total_swimmers);
-    execute!(
-        stdout,
-        cursor::MoveTo((terminal_width as u16).saturating_sub(swimmers_text.len() as u16 + 1), 2),
-        SetForegroundColor(Color::Cyan),
-        Print(swimmers_text),
-        ResetColor
-    ).into_diagnostic()?;
+    // Swimmers count out of the lanes the pool has - always on line 2, right side
+    let swimmers_text = format!("Swimmers: {} / {}", total_swimmers, game.pool().lanes());
+    frame.set_string(
+        (terminal_width as u16).saturating_sub(text_width(&swimmers_text) as u16 + 1),
+        2,
+        &swimmers_text,
+        Style::default().fg(Color::Cyan)
+    );
 
-    // Enhanced instructions with better formatting - always on line 3
-    let instructions: &str = "Controls: [↑/↓] Select | [Space] Upgrade | [n] New Swimmer | [q] Quit";
-    let instructions_padding: usize = center_padding(instructions.len(), terminal_width);
-    execute!(
-        stdout,
-        cursor::MoveTo(instructions_padding as u16, 3),
-        SetForegroundColor(Color::Yellow),
-        Print(instructions),
-        ResetColor
-    ).into_diagnostic()?;
+    // Controls from the keymap in use, so they always name the right keys - always on line 3
+    let keymap: &Keymap = game.keymap();
+    let controls: Vec<String> = [
+        keymap.hint(&[Action::SelectPrev, Action::SelectNext], "Select"),
+        keymap.hint(&[Action::Toggle], "Upgrades"),
+        keymap.hint(&[Action::Rest], "Rest"),
+        keymap.hint(&[Action::Hire], "Hire"),
+        keymap.hint(&[Action::Quit], "Quit"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let instructions: String = format!("Controls: {}", controls.join(" | "));
+    let instructions_padding: usize = center_padding(text_width(&instructions), terminal_width);
+    frame.set_string(instructions_padding as u16, 3, &instructions, Style::default().fg(Color::Yellow));
     
+    // Meet open for entries - line 4, centered, once one has been announced
+    if let Some(meet) = game.meet() {
+        let meet_text: String = format!(
+            "🏁 {}: {} lengths, {} prize money - {} Enter 🏁",
+            meet.name,
+            meet.distance,
+            meet.prize,
+            keymap.key(Action::Meet)
+        );
+        let meet_padding: usize = center_padding(text_width(&meet_text), terminal_width);
+        frame.set_string(meet_padding as u16, 4, &meet_text, Style::default().fg(Color::Magenta).bold());
+    }
+
     // New swimmer cost with box styling - always on line 5
-    let new_swimmer_info: String = format!("[ New Swimmer Cost: {} lengths ]", new_swimmer_cost);
-    let info_padding: usize = center_padding(new_swimmer_info.len(), terminal_width);
-    execute!(
-        stdout,
-        cursor::MoveTo(info_padding as u16, 5),
-        SetForegroundColor(Color::Green),
-        SetAttribute(Attribute::Bold),
-        Print(&new_swimmer_info),
-        SetAttribute(Attribute::Reset),
-        ResetColor
-    ).into_diagnostic()?;
+    let new_swimmer_info: String = format!("[ New Swimmer Cost: {} lengths ]", game.new_swimmer_cost());
+    let info_padding: usize = center_padding(text_width(&new_swimmer_info), terminal_width);
+    frame.set_string(
+        info_padding as u16,
+        5,
+        &new_swimmer_info,
+        Style::default().fg(Color::Green).bold()
+    );
+    
+    // Trophies carried over from earlier seasons - line 5, left side, once a season has ended
+    let prestige = game.prestige();
+    if prestige.seasons > 0 {
+        frame.set_string(
+            1,
+            5,
+            &format!("Season {} | Trophies: {}", prestige.seasons + 1, prestige.trophies),
+            Style::default().fg(Color::Yellow)
+        );
+    }
+    
+    // Prize money and medals won at meets - line 5, right side, once the team has raced
+    let medals = game.medals();
+    if wallet.prize_money > 0 || medals.total() > 0 {
+        let prize_text: String = format!("Prize: {} | Medals: {}", wallet.prize_money, medals.total());
+        frame.set_string(
+            (terminal_width as u16).saturating_sub(text_width(&prize_text) as u16 + 1),
+            5,
+            &prize_text,
+            Style::default().fg(Color::Yellow)
+        );
+    }
     
     // Draw a separator line - always on line 6
     let separator: String = "─".repeat(terminal_width);
-    execute!(
-        stdout,
-        cursor::MoveTo(0, 6),
-        SetForegroundColor(Color::DarkGrey),
-        Print(&separator),
-        ResetColor
-    ).into_diagnostic()?;
-    
-    Ok(())
+    frame.set_string(0, 6, &separator, Style::default().fg(Color::DarkGrey));
 }
@@ -0,0 +1,117 @@
+use swimming_idle::game::FRAME_DURATION_MS;
+use swimming_idle::league::{Club, League, DIVISIONS, PROMOTION_PLACES, WEEKS_PER_SEASON};
+use swimming_idle::race::Meet;
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Renders the league screen: the team's division table and the weekly meets still to come
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `league` - The league the team plays in
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+/// * `bottom_row` - First row kept free for the footer
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_league(
+    frame: &mut FrameBuffer,
+    league: &League,
+    terminal_width: usize,
+    header_height: u16,
+    bottom_row: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+    let division: usize = league.player_division();
+
+    let next_secs: u64 = league.ticks_to_next_week() * FRAME_DURATION_MS / 1000;
+    let title: String = format!(
+        "🏆 Division {} of {} | Week {} / {} | Next meet in {}s 🏆",
+        division + 1,
+        DIVISIONS,
+        league.week,
+        WEEKS_PER_SEASON,
+        next_secs
+    );
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Magenta).bold());
+    current_row += 2;
+
+    // Standings table, with the promotion and relegation places coloured in
+    let table_header: String = format!("{:>3}  {:<28} {:>4} {:>4} {:>4}", "Pos", "Club", "Pld", "W", "Pts");
+    let table_padding: usize = center_padding(text_width(&table_header), terminal_width);
+    frame.set_string(table_padding as u16, current_row, &table_header, Style::default().fg(Color::Cyan).bold());
+    current_row += 1;
+
+    let standings: Vec<&Club> = league.standings();
+    let clubs: usize = standings.len();
+    for (i, club) in standings.iter().enumerate() {
+        let promoted: bool = division > 0 && i < PROMOTION_PLACES;
+        let relegated: bool = division < DIVISIONS - 1 && i + PROMOTION_PLACES >= clubs;
+
+        let mut style: Style = if promoted {
+            Style::default().fg(Color::Green)
+        } else if relegated {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::Grey)
+        };
+        if club.is_player {
+            style = style.bg(Color::DarkGrey).bold();
+        }
+
+        let row: String = format!(
+            "{:>3}  {:<28} {:>4} {:>4} {:>4}",
+            i + 1,
+            club.name,
+            club.played,
+            club.wins,
+            club.points
+        );
+        frame.set_string(table_padding as u16, current_row, &row, style);
+        current_row += 1;
+    }
+    current_row += 1;
+
+    // The top division has nowhere to go up to and the bottom one nowhere to go down to
+    let mut rules: Vec<String> = Vec::new();
+    if division > 0 {
+        rules.push(format!("Top {} go up", PROMOTION_PLACES));
+    }
+    if division < DIVISIONS - 1 {
+        rules.push(format!("Bottom {} go down", PROMOTION_PLACES));
+    }
+    rules.push(format!("Winning a meet pays {} prize money", league.prize_pool()));
+    let rules: String = rules.join(" | ");
+    let rules_padding: usize = center_padding(text_width(&rules), terminal_width);
+    frame.set_string(rules_padding as u16, current_row, &rules, Style::default().fg(Color::DarkGrey));
+    current_row += 2;
+
+    // The season calendar, cut off where the footer starts
+    if current_row + 1 >= bottom_row {
+        return current_row;
+    }
+    frame.set_string(table_padding as u16, current_row, "Upcoming fixtures:", Style::default().fg(Color::Cyan).bold());
+    current_row += 1;
+
+    let fixtures: &[Meet] = league.upcoming_fixtures();
+    for (i, fixture) in fixtures.iter().enumerate() {
+        if current_row >= bottom_row {
+            break;
+        }
+        let line: String = format!(
+            "Week {}: {} | {} lengths | Prize: {}",
+            league.week as usize + i + 1,
+            fixture.name,
+            fixture.distance,
+            fixture.prize
+        );
+        frame.set_string(table_padding as u16 + 2, current_row, &line, Style::default().fg(Color::Grey));
+        current_row += 1;
+    }
+
+    current_row
+}
@@ -1,67 +1,216 @@
 //! Display module for handling the game UI rendering
 
+mod achievements;
+mod buffer;
+mod details;
+mod facilities;
 mod header;
+mod league;
 mod swimmers;
 mod footer;
+mod race;
+mod relay;
+mod season;
+mod shop;
+mod upgrade;
+mod viewport;
+mod widgets;
 
-use crate::swimmer::Swimmer;
-use miette::{IntoDiagnostic, Result};
-use std::io::{stdout, Write};
+pub use crate::ui::display::buffer::Renderer;
+pub use crate::ui::display::viewport::Viewport;
+
+use swimming_idle::game::{Game, Screen};
+use swimming_idle::notifications::MAX_NOTIFICATIONS;
+use crate::ui::display::buffer::FrameBuffer;
+use miette::Result;
+use std::io::stdout;
+use std::ops::Range;
 use terminal_size::{terminal_size, Height, Width};
 
-use crossterm::{
-    cursor, execute,
-    terminal::{Clear, ClearType},
-};
+/// Rows used by each swimmer: stats, lane and divider
+const LANE_HEIGHT: u16 = 3;
+
+/// Rows kept free below the lanes for the footer line and the notification stack
+const FOOTER_HEIGHT: u16 = 3 + MAX_NOTIFICATIONS as u16;
 
-/// Displays the main game UI with all swimmers and game information
+/// Displays the main game UI for the screen the player is on
+/// 
+/// The frame is drawn into an in-memory buffer first and only the cells that
+/// changed since the previous frame are sent to the terminal.
 /// 
 /// # Arguments
-/// * `swimmers` - Slice of swimmer objects to display
-/// * `selected_index` - Index of the currently selected swimmer
-/// * `new_swimmer_cost` - Cost to add a new swimmer
+/// * `renderer` - Renderer holding the previously drawn frame
+/// * `viewport` - Which part of the swimmer list is on screen, scrolled to follow the selection
+/// * `game` - The game state to draw
 /// 
 /// # Returns
 /// A Result indicating success or an error
-pub fn display_ui(swimmers: &[Swimmer], selected_index: usize, new_swimmer_cost: usize) -> Result<()> {
-    let mut stdout = stdout();
-    
-    // Ensure the terminal is completely cleared before each redraw
-    execute!(
-        stdout, 
-        Clear(ClearType::All), 
-        cursor::MoveTo(0, 0)
-    ).into_diagnostic()?;
-    
-    // Hide cursor during rendering
-    execute!(stdout, cursor::Hide).into_diagnostic()?;
-    
+pub fn display_ui(renderer: &mut Renderer, viewport: &mut Viewport, game: &Game) -> Result<()> {
     // Get terminal dimensions
     let terminal_dimensions =
         terminal_size().ok_or_else(|| miette::miette!("Failed to get terminal size"))?;
-    let (Width(width), Height(_)) = terminal_dimensions;
+    let (Width(width), Height(height)) = terminal_dimensions;
     let terminal_width: usize = width as usize;
 
+    let mut frame: FrameBuffer = FrameBuffer::new(width, height);
+
     // Define a consistent header height
     let header_height: u16 = 7; // Fixed header size
 
     // Render header section
-    header::render_header(&mut stdout, terminal_width, swimmers, new_swimmer_cost)?;
-    
-    // Render all swimmers
-    let current_row = swimmers::render_swimmers(&mut stdout, swimmers, selected_index, terminal_width, header_height)?;
-    
+    header::render_header(&mut frame, terminal_width, game);
+
+    // Only the pool screen scrolls, the menus always fit
+    let swimmers = game.swimmers();
+    let (current_row, visible): (u16, Range<usize>) = match game.screen() {
+        Screen::Pool => {
+            // Fit as many lanes as the terminal has room for below the relays and scroll to the selection
+            let relays_height: u16 = game.relays().len() as u16 * LANE_HEIGHT;
+            let lanes_height: u16 = height.saturating_sub(header_height + 1 + FOOTER_HEIGHT + relays_height);
+            viewport.follow((lanes_height / LANE_HEIGHT) as usize, game.selected_index(), swimmers.len());
+            let visible: Range<usize> = viewport.visible_range(swimmers.len());
+
+            // Render the visible swimmers
+            let current_row = swimmers::render_swimmers(
+                &mut frame,
+                swimmers,
+                game.relays(),
+                game.selected_index(),
+                visible.clone(),
+                terminal_width,
+                header_height
+            );
+            (current_row, visible)
+        }
+        Screen::Upgrade { selected_index, retrain_to } => {
+            // Keep the selected swimmer's lane in view above its upgrade menu
+            let selected: usize = game.selected_index();
+            let current_row = swimmers::render_swimmers(
+                &mut frame,
+                swimmers,
+                &[],
+                selected,
+                selected..selected + 1,
+                terminal_width,
+                header_height
+            );
+            let current_row = upgrade::render_upgrade_menu(
+                &mut frame,
+                game,
+                selected_index,
+                retrain_to,
+                terminal_width,
+                current_row
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Details { selected_index } => {
+            let current_row = details::render_details(
+                &mut frame,
+                game,
+                selected_index,
+                terminal_width,
+                header_height,
+                height.saturating_sub(FOOTER_HEIGHT)
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Shop { selected_index } => {
+            let current_row = shop::render_shop(
+                &mut frame,
+                game.shop(),
+                game.wallet(),
+                selected_index,
+                terminal_width,
+                header_height
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Season { selected_index } => {
+            let current_row = season::render_season(
+                &mut frame,
+                game.prestige(),
+                game.season_lengths(),
+                selected_index,
+                terminal_width,
+                header_height
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Meet { selected_index } => {
+            let current_row = race::render_meet_entry(
+                &mut frame,
+                game,
+                selected_index,
+                terminal_width,
+                header_height,
+                height.saturating_sub(FOOTER_HEIGHT)
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Race => {
+            let current_row = match game.race() {
+                Some(heat) => race::render_race(&mut frame, heat, terminal_width, header_height),
+                None => header_height + 1,
+            };
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Relay { selected_index } => {
+            let current_row = relay::render_relay_picker(
+                &mut frame,
+                game,
+                selected_index,
+                terminal_width,
+                header_height,
+                height.saturating_sub(FOOTER_HEIGHT)
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Facilities { selected_index } => {
+            let current_row = facilities::render_facilities(
+                &mut frame,
+                game.pool(),
+                game.wallet(),
+                swimmers.len(),
+                selected_index,
+                terminal_width,
+                header_height
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::Achievements { selected_index } => {
+            let current_row = achievements::render_achievements(
+                &mut frame,
+                game.achievements(),
+                game.achievement_list(),
+                selected_index,
+                terminal_width,
+                header_height,
+                height.saturating_sub(FOOTER_HEIGHT)
+            );
+            (current_row, 0..swimmers.len())
+        }
+        Screen::League => {
+            let current_row = league::render_league(
+                &mut frame,
+                game.league(),
+                terminal_width,
+                header_height,
+                height.saturating_sub(FOOTER_HEIGHT)
+            );
+            (current_row, 0..swimmers.len())
+        }
+    };
+
     // Render footer
-    footer::render_footer(&mut stdout, current_row, terminal_width)?;
+    footer::render_footer(
+        &mut frame,
+        current_row,
+        terminal_width,
+        game,
+        visible
+    );
 
-    // Show cursor again
-    execute!(stdout, cursor::Show).into_diagnostic()?;
-    
-    // Flush all changes to ensure immediate display
-    stdout.flush().into_diagnostic()?;
-    
-    // Ensure the terminal is properly refreshed
-    execute!(stdout, cursor::MoveTo(0, 0)).into_diagnostic()?;
-    
-    Ok(())
+    // Send only the changed cells, flushed once
+    renderer.draw(&mut stdout(), frame)
 }
@@ -0,0 +1,181 @@
+use swimming_idle::game::{Game, FRAME_DURATION_MS};
+use swimming_idle::race::{medal, ordinal, Meet, Race, Racer, MAX_ENTRANTS};
+use swimming_idle::swimmer::{Swimmer, LANE_LENGTH};
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::display::swimmers::render_swimmer_lane;
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Renders the entry screen of the open meet, listing every swimmer that could be entered
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `game` - The game state, for the meet, the swimmers and who is entered
+/// * `selected_index` - Index of the highlighted swimmer
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+/// * `bottom_row` - First row kept free for the footer
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_meet_entry(
+    frame: &mut FrameBuffer,
+    game: &Game,
+    selected_index: usize,
+    terminal_width: usize,
+    header_height: u16,
+    bottom_row: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+    let Some(meet) = game.meet() else {
+        return current_row;
+    };
+
+    current_row = render_meet_title(frame, meet, terminal_width, current_row);
+
+    let entrants: &[usize] = game.entrants();
+    let subtitle: String = format!("Entered: {} / {}", entrants.len(), MAX_ENTRANTS);
+    let subtitle_padding: usize = center_padding(text_width(&subtitle), terminal_width);
+    frame.set_string(subtitle_padding as u16, current_row, &subtitle, Style::default().fg(Color::Cyan));
+    current_row += 2;
+
+    // Scroll the list so the highlighted swimmer is always on screen
+    let swimmers: &[Swimmer] = game.swimmers();
+    let rows: usize = (bottom_row.saturating_sub(current_row + 1) as usize).max(1);
+    let first: usize = selected_index.saturating_sub(rows - 1);
+    let modifiers = game.modifiers();
+
+    for (i, swimmer) in swimmers.iter().enumerate().skip(first).take(rows) {
+        let is_selected: bool = i == selected_index;
+        let is_entered: bool = entrants.contains(&i);
+
+        let style: Style = if is_entered {
+            Style::default().fg(Color::Cyan).bold()
+        } else {
+            entry_style(true)
+        };
+
+        let line: String = format!(
+            "{} [{}] {} | {} | Speed: {:.2} | Energy: {:.0}% | Medals: {}",
+            if is_selected { "➤" } else { " " },
+            if is_entered { "x" } else { " " },
+            swimmer.name,
+            swimmer.stroke.name(),
+            swimmer.effective_speed(&modifiers),
+            swimmer.energy() * 100_f64,
+            swimmer.medals.total()
+        );
+        render_entry(frame, &line, is_selected, style, terminal_width, current_row);
+        current_row += 1;
+    }
+
+    current_row
+}
+
+/// Renders the heat being swum, one lane per swimmer, with their place once they finish
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `race` - The heat to draw
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_race(
+    frame: &mut FrameBuffer,
+    race: &Race,
+    terminal_width: usize,
+    header_height: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+    current_row = render_meet_title(frame, &race.meet, terminal_width, current_row);
+
+    let tick_secs: f64 = FRAME_DURATION_MS as f64 / 1000_f64;
+    let clock: String = if race.is_finished() {
+        "Race over!".to_string()
+    } else {
+        format!("⏱ {:.2}s", race.elapsed() as f64 * tick_secs)
+    };
+    let clock_padding: usize = center_padding(text_width(&clock), terminal_width);
+    frame.set_string(clock_padding as u16, current_row, &clock, Style::default().fg(Color::Cyan).bold());
+    current_row += 2;
+
+    let lane_width: usize = terminal_width.saturating_sub(10);
+    let standings: Vec<(usize, usize)> = race.standings();
+    for (lane, racer) in race.racers().iter().enumerate() {
+        let place: usize = standings
+            .iter()
+            .find(|(standing_lane, _)| *standing_lane == lane)
+            .map_or(0, |(_, place)| *place);
+
+        render_racer_info(frame, race, racer, lane, place, terminal_width, current_row);
+        current_row += 1;
+        render_swimmer_lane(frame, &racer.swimmer, lane_width, terminal_width, current_row);
+        current_row += 1;
+    }
+
+    current_row
+}
+
+/// Renders the name, distance and prize of a meet
+fn render_meet_title(
+    frame: &mut FrameBuffer,
+    meet: &Meet,
+    terminal_width: usize,
+    row: u16
+) -> u16 {
+    let title: String = format!(
+        "🏁 {} | {} lengths | Prize: {} 🏁",
+        meet.name,
+        meet.distance,
+        meet.prize
+    );
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, row, &title, Style::default().fg(Color::Magenta).bold());
+    row + 2
+}
+
+/// Renders the line above a racer's lane: who they are and how their race is going
+fn render_racer_info(
+    frame: &mut FrameBuffer,
+    race: &Race,
+    racer: &Racer,
+    lane: usize,
+    place: usize,
+    terminal_width: usize,
+    row: u16
+) {
+    let status: String = match racer.finish {
+        Some(finish) => format!(
+            "{} in {:.2}s {}",
+            ordinal(place),
+            finish * FRAME_DURATION_MS as f64 / 1000_f64,
+            medal(place)
+        ),
+        None => format!(
+            "Length {} / {}",
+            ((racer.swimmer.progress / LANE_LENGTH) as u64 + 1).min(race.meet.distance),
+            race.meet.distance
+        ),
+    };
+
+    // The team's own swimmers stand out from the rivals
+    let style: Style = if racer.team_index.is_some() {
+        Style::default().fg(Color::Cyan).bold()
+    } else {
+        Style::default().fg(Color::Grey)
+    };
+    let info: String = format!(
+        "Lane {} | {} ({}) | {} | {}",
+        lane + 1,
+        racer.swimmer.name,
+        racer.club,
+        racer.swimmer.stroke.name(),
+        status
+    );
+    let info_padding: usize = center_padding(text_width(&info), terminal_width);
+    frame.set_string(info_padding as u16, row, &info, style);
+}
@@ -0,0 +1,88 @@
+use swimming_idle::game::Game;
+use swimming_idle::relay::{LEG_LENGTHS, RELAY_BONUS_LENGTHS, RELAY_SIZE};
+use swimming_idle::swimmer::Swimmer;
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Renders the relay screen, listing every swimmer with the leg they were picked for
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `game` - The game state, for the swimmers, the relays and the picks so far
+/// * `selected_index` - Index of the highlighted swimmer
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+/// * `bottom_row` - First row kept free for the footer
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_relay_picker(
+    frame: &mut FrameBuffer,
+    game: &Game,
+    selected_index: usize,
+    terminal_width: usize,
+    header_height: u16,
+    bottom_row: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+
+    let title: String = format!(
+        "🔁 New Relay | {} legs of {} lengths | +{} bonus lengths a relay 🔁",
+        RELAY_SIZE,
+        LEG_LENGTHS,
+        RELAY_BONUS_LENGTHS * game.pool().length.scale()
+    );
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Magenta).bold());
+    current_row += 2;
+
+    let picks: &[usize] = game.relay_picks();
+    let subtitle: String = format!("Picked: {} / {}", picks.len(), RELAY_SIZE);
+    let subtitle_padding: usize = center_padding(text_width(&subtitle), terminal_width);
+    frame.set_string(subtitle_padding as u16, current_row, &subtitle, Style::default().fg(Color::Cyan));
+    current_row += 2;
+
+    // Scroll the list so the highlighted swimmer is always on screen
+    let swimmers: &[Swimmer] = game.swimmers();
+    let rows: usize = (bottom_row.saturating_sub(current_row + 1) as usize).max(1);
+    let first: usize = selected_index.saturating_sub(rows - 1);
+
+    for (i, swimmer) in swimmers.iter().enumerate().skip(first).take(rows) {
+        let is_selected: bool = i == selected_index;
+        let leg: Option<usize> = picks.iter().position(|pick| *pick == i);
+        let relay_name: Option<&str> = game
+            .relays()
+            .iter()
+            .find(|relay| relay.members.contains(&i))
+            .map(|relay| relay.name.as_str());
+
+        // Swimmers already in a relay are greyed out, picked ones stand out
+        let style: Style = if leg.is_some() {
+            Style::default().fg(Color::Cyan).bold()
+        } else {
+            entry_style(relay_name.is_none())
+        };
+
+        let slot: String = match (leg, relay_name) {
+            (Some(leg), _) => format!("Leg {}", leg + 1),
+            (None, Some(name)) => name.to_string(),
+            (None, None) => "     ".to_string(),
+        };
+        let line: String = format!(
+            "{} [{}] {} | {} | Speed: {} | Energy: {:.0}%",
+            if is_selected { "➤" } else { " " },
+            slot,
+            swimmer.name,
+            swimmer.stroke.name(),
+            swimmer.display_speed(),
+            swimmer.energy() * 100_f64
+        );
+        render_entry(frame, &line, is_selected, style, terminal_width, current_row);
+        current_row += 1;
+    }
+
+    current_row
+}
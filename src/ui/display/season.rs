@@ -0,0 +1,77 @@
+use swimming_idle::prestige::{Prestige, PrestigeItem};
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Renders the season screen with the prestige bonuses and the option to retire the club
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `prestige` - Trophies and bonus levels bought so far
+/// * `season_lengths` - Lifetime lengths of the current season, for the trophy preview
+/// * `selected_index` - Index of the highlighted entry
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_season(
+    frame: &mut FrameBuffer,
+    prestige: &Prestige,
+    season_lengths: u64,
+    selected_index: usize,
+    terminal_width: usize,
+    header_height: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+
+    let title: String = format!(
+        "🏆 Season {} | Trophies: {} 🏆",
+        prestige.seasons + 1,
+        prestige.trophies
+    );
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Yellow).bold());
+    current_row += 2;
+
+    for (i, item) in PrestigeItem::ALL.iter().enumerate() {
+        let price: u64 = prestige.price(*item);
+        let line: String = format!(
+            "{} {} (Level {}) | {} | Next: {} trophies",
+            if i == selected_index { "➤" } else { " " },
+            item.name(),
+            prestige.level(*item),
+            item.description(),
+            price
+        );
+        render_entry(frame, &line, i == selected_index, entry_style(prestige.trophies >= price), terminal_width, current_row);
+        current_row += 2;
+    }
+
+    // Retiring is listed last so it is never bought by accident while scrolling the bonuses
+    let trophies: u64 = Prestige::trophies_for(season_lengths);
+    let line: String = format!(
+        "{} 🏁 Start a new season | Earns {} trophies | Next trophy at {} lifetime lengths",
+        if selected_index == PrestigeItem::ALL.len() { "➤" } else { " " },
+        trophies,
+        Prestige::lengths_for(trophies + 1)
+    );
+    render_entry(
+        frame,
+        &line,
+        selected_index == PrestigeItem::ALL.len(),
+        entry_style(trophies > 0),
+        terminal_width,
+        current_row
+    );
+    current_row += 1;
+
+    let warning: &str = "A new season resets swimmers, hiring costs, lengths and points, prize money is kept";
+    let warning_padding: usize = center_padding(text_width(warning), terminal_width);
+    frame.set_string(warning_padding as u16, current_row, warning, Style::default().fg(Color::DarkGrey));
+    current_row += 1;
+
+    current_row
+}
@@ -0,0 +1,53 @@
+use swimming_idle::shop::{Shop, ShopItem};
+use swimming_idle::wallet::Wallet;
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Renders the points shop with every permanent upgrade and its next price
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `shop` - Levels bought so far
+/// * `wallet` - The team wallet, to grey out items the team cannot afford
+/// * `selected_index` - Index of the highlighted item
+/// * `terminal_width` - Width of the terminal
+/// * `header_height` - Height of the header section
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_shop(
+    frame: &mut FrameBuffer,
+    shop: &Shop,
+    wallet: &Wallet,
+    selected_index: usize,
+    terminal_width: usize,
+    header_height: u16
+) -> u16 {
+    let mut current_row: u16 = header_height + 1;
+
+    let title: &str = "🛒 Points Shop 🛒";
+    let title_padding: usize = center_padding(text_width(title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, title, Style::default().fg(Color::Magenta).bold());
+    current_row += 2;
+
+    for (i, item) in ShopItem::ALL.iter().enumerate() {
+        let is_selected: bool = i == selected_index;
+        let price: u64 = shop.price(*item);
+
+        let line: String = format!(
+            "{} {} (Level {}) | {} | Next: {} points",
+            if is_selected { "➤" } else { " " },
+            item.name(),
+            shop.level(*item),
+            item.description(),
+            price
+        );
+        render_entry(frame, &line, is_selected, entry_style(wallet.points >= price), terminal_width, current_row);
+        current_row += 2;
+    }
+
+    current_row
+}
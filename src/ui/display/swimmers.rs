@@ -1,112 +1,157 @@
-use crate::swimmer::Swimmer;
-use crate::ui::utils::center_padding;
-use miette::{IntoDiagnostic, Result};
-use std::io::Write;
+use swimming_idle::game::FRAME_DURATION_MS;
+use swimming_idle::relay::{Relay, RELAY_SIZE};
+use swimming_idle::swimmer::{Stat, Swimmer, LANE_LENGTH};
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::utils::{center_padding, text_width};
 
-use crossterm::{
-    cursor, execute,
-    style::{Color, Print, ResetColor, SetForegroundColor, SetBackgroundColor, Attribute, SetAttribute},
-};
+use crossterm::style::Color;
+use std::ops::Range;
 
-/// Renders all swimmers with their lanes and indicators
+/// Renders the relay lanes and the visible swimmers with their lanes and indicators
 /// 
 /// # Arguments
-/// * `stdout` - The output stream to write to
+/// * `frame` - The frame buffer to draw into
 /// * `swimmers` - Slice of swimmer objects to display
+/// * `relays` - Relay teams, each drawn in its own lane above the swimmers
 /// * `selected_index` - Index of the currently selected swimmer
+/// * `visible` - Indices of the swimmers that fit on screen
 /// * `terminal_width` - Width of the terminal
 /// * `header_height` - Height of the header section
 /// 
 /// # Returns
-/// Result with the current row position after rendering (for footer positioning)
-pub fn render_swimmers<W: Write>(
-    stdout: &mut W, 
+/// The current row position after rendering (for footer positioning)
+pub fn render_swimmers(
+    frame: &mut FrameBuffer, 
     swimmers: &[Swimmer], 
+    relays: &[Relay],
     selected_index: usize, 
+    visible: Range<usize>,
     terminal_width: usize,
     header_height: u16
-) -> Result<u16> {
+) -> u16 {
     // Lane width calculation
     let lane_width: usize = terminal_width.saturating_sub(10);
 
     // Display swimmer lanes and positions - starts exactly after header
     let mut current_row: u16 = header_height + 1;
+
+    // Relays share a lane, drawn with whoever is swimming their leg
+    for relay in relays {
+        let swimmer: &Swimmer = &swimmers[relay.active_member()];
+        render_relay_stats(frame, relay, swimmer, terminal_width, current_row);
+        current_row += 1;
+
+        render_swimmer_lane(frame, swimmer, lane_width, terminal_width, current_row);
+        current_row += 1;
+
+        render_lane_divider(frame, terminal_width, current_row);
+        current_row += 1;
+    }
     
-    for (i, swimmer) in swimmers.iter().enumerate() {
+    for (i, swimmer) in swimmers.iter().enumerate().take(visible.end).skip(visible.start) {
         let is_selected: bool = i == selected_index;
         
         // Render swimmer stats bar
-        render_swimmer_stats(stdout, swimmer, is_selected, terminal_width, current_row)?;
+        render_swimmer_stats(frame, swimmer, is_selected, terminal_width, current_row);
         current_row += 1;
         
-        // Render swimmer lane
-        render_swimmer_lane(stdout, swimmer, lane_width, terminal_width, current_row)?;
+        // Render swimmer lane, relay members swim in their relay's lane instead
+        match relays.iter().find(|relay| relay.members.contains(&i)) {
+            Some(relay) => render_relay_member_lane(frame, relay, i, lane_width, terminal_width, current_row),
+            None => render_swimmer_lane(frame, swimmer, lane_width, terminal_width, current_row),
+        }
         current_row += 1;
         
         // Render divider
-        render_lane_divider(stdout, terminal_width, current_row)?;
+        render_lane_divider(frame, terminal_width, current_row);
         current_row += 1;
     }
     
-    Ok(current_row)
+    current_row
 }
 
 /// Renders the stats for a single swimmer
-fn render_swimmer_stats<W: Write>(
-    stdout: &mut W,
+fn render_swimmer_stats(
+    frame: &mut FrameBuffer,
     swimmer: &Swimmer,
     is_selected: bool,
     terminal_width: usize,
     row: u16
-) -> Result<()> {
-    // Box styling for selected swimmer
-    if is_selected {
-        let box_width: usize = terminal_width.saturating_sub(6);
-        execute!(
-            stdout,
-            cursor::MoveTo(3, row),
-            SetBackgroundColor(Color::DarkGrey),
-            Print(" ".repeat(box_width)),
-            ResetColor
-        ).into_diagnostic()?;
-    }
-    
+) {
     // Swimmer stats with better formatting
     let stats: String = format!(
-        "{} {} | Speed: {} | Lengths: {} | Next Upgrade: {} lengths",
+        "{} {} | Lv {}{} | {} | Speed: {} | Energy: {:.0}%{} | Lifetime: {} lengths",
         if is_selected { "➤" } else { " " },
         swimmer.name,
-        swimmer.display_speed(),
-        swimmer.lengths,
-        swimmer.upgrade_cost
+        swimmer.level(),
+        if swimmer.unclaimed_perks() > 0 { " ⭐" } else { "" },
+        swimmer.stroke.name(),
+        swimmer.display_stat(Stat::Speed),
+        swimmer.energy() * 100_f64,
+        if swimmer.resting { " (resting)" } else { "" },
+        swimmer.lifetime_lengths
     );
-    let stats_padding: usize = center_padding(stats.len(), terminal_width);
+    render_entry(frame, &stats, is_selected, entry_style(true), terminal_width, row);
+}
 
-    // Highlight selected swimmer
-    if is_selected {
-        execute!(stdout, SetForegroundColor(Color::White), SetAttribute(Attribute::Bold)).into_diagnostic()?;
-    } else {
-        execute!(stdout, SetForegroundColor(Color::Grey)).into_diagnostic()?;
-    }
+/// Renders the stats of a relay team and the member swimming their leg
+fn render_relay_stats(
+    frame: &mut FrameBuffer,
+    relay: &Relay,
+    swimmer: &Swimmer,
+    terminal_width: usize,
+    row: u16
+) {
+    let tick_secs: f64 = FRAME_DURATION_MS as f64 / 1000_f64;
+    let best: String = relay
+        .best_ticks
+        .map_or("-".to_string(), |ticks| format!("{:.2}s", ticks as f64 * tick_secs));
+    let stats: String = format!(
+        "🔁 {} | Leg {}/{}: {} ({}) | ⏱ {:.2}s | Best: {} | Completed: {}",
+        relay.name,
+        relay.leg(),
+        RELAY_SIZE,
+        swimmer.name,
+        swimmer.stroke.name(),
+        relay.ticks() as f64 * tick_secs,
+        best,
+        relay.completed
+    );
+    let stats_padding: usize = center_padding(text_width(&stats), terminal_width);
 
-    execute!(
-        stdout,
-        cursor::MoveTo(stats_padding as u16, row),
-        Print(&stats),
-        ResetColor
-    ).into_diagnostic()?;
-    
-    Ok(())
+    frame.set_string(stats_padding as u16, row, &stats, Style::default().fg(Color::Cyan).bold());
 }
 
-/// Renders a swimming lane with the swimmer at the correct position
-fn render_swimmer_lane<W: Write>(
-    stdout: &mut W,
-    swimmer: &Swimmer,
+/// Renders the lane of a relay member, who swims in their relay's lane instead
+fn render_relay_member_lane(
+    frame: &mut FrameBuffer,
+    relay: &Relay,
+    index: usize,
     lane_width: usize,
     terminal_width: usize,
     row: u16
-) -> Result<()> {
+) {
+    let lane_padding: usize = render_empty_lane(frame, lane_width, terminal_width, row);
+
+    let note: String = if relay.active_member() == index {
+        format!("🔁 Swimming leg {} for {}", relay.leg(), relay.name)
+    } else {
+        format!("🔁 Waiting on deck with {}", relay.name)
+    };
+    frame.set_string((lane_padding + 1) as u16, row, &note, Style::default().fg(Color::DarkGrey));
+}
+
+/// Renders a lane with no one in it
+///
+/// # Returns
+/// The column the lane's left wall is drawn at
+fn render_empty_lane(
+    frame: &mut FrameBuffer,
+    lane_width: usize,
+    terminal_width: usize,
+    row: u16
+) -> usize {
     // Calculate lane padding
     let lane_padding: usize = center_padding(lane_width + 2, terminal_width);
     
@@ -119,53 +164,71 @@ fn render_swimmer_lane<W: Write>(
     lane.push('│'); // Right wall
 
     // Draw the base lane with water color
-    execute!(
-        stdout,
-        cursor::MoveTo(lane_padding as u16, row),
-        SetForegroundColor(Color::Blue),
-        Print(&lane),
-        ResetColor
-    ).into_diagnostic()?;
-
-    // Calculate swimmer position in the lane
-    let position_in_lane: usize = swimmer.position * lane_width / 100;
+    frame.set_string(lane_padding as u16, row, &lane, Style::default().fg(Color::Blue));
+    lane_padding
+}
+
+/// Renders a swimming lane with the swimmer at the correct position
+pub fn render_swimmer_lane(
+    frame: &mut FrameBuffer,
+    swimmer: &Swimmer,
+    lane_width: usize,
+    terminal_width: usize,
+    row: u16
+) {
+    let lane_padding: usize = render_empty_lane(frame, lane_width, terminal_width, row);
+
+    // A resting swimmer sits on the bench by the left wall instead of swimming
+    if swimmer.resting {
+        frame.set_string(
+            (lane_padding + 1) as u16,
+            row,
+            "💤",
+            Style::default().fg(Color::DarkGrey)
+        );
+        return;
+    }
+
+    // Calculate swimmer position in the lane, keeping the two-cell glyph inside the walls
+    let position_in_lane: usize = ((swimmer.position * lane_width as f64 / LANE_LENGTH) as usize)
+        .min(lane_width.saturating_sub(2));
     
-    // Draw the swimmer with distinctive color based on speed
-    let swimmer_color: Color = match swimmer.speed as usize {
-        0..=1 => Color::Red,
-        2..=3 => Color::Yellow,
-        _ => Color::Green,
+    // Draw the swimmer with distinctive color based on speed, greyed out once they tire
+    let swimmer_color: Color = if swimmer.is_tired() {
+        Color::DarkGrey
+    } else {
+        match swimmer.speed as usize {
+            0..=1 => Color::Red,
+            2..=3 => Color::Yellow,
+            _ => Color::Green,
+        }
     };
     
-    execute!(
-        stdout,
-        cursor::MoveTo((lane_padding + position_in_lane + 1) as u16, row),
-        SetForegroundColor(swimmer_color),
-        SetAttribute(Attribute::Bold),
-        Print(if swimmer.direction { "-→" } else { "←-" }),
-        SetAttribute(Attribute::Reset),
-        ResetColor
-    ).into_diagnostic()?;
-    
-    Ok(())
+    // Animate the stroke as the swimmer covers distance, a frame every few units
+    let frames: [char; 2] = swimmer.current_stroke().frames();
+    let body: char = frames[(swimmer.progress / 3.0) as usize % frames.len()];
+    let glyph: String = if swimmer.direction {
+        format!("{}→", body)
+    } else {
+        format!("←{}", body)
+    };
+
+    frame.set_string(
+        (lane_padding + position_in_lane + 1) as u16,
+        row,
+        &glyph,
+        Style::default().fg(swimmer_color).bold()
+    );
 }
 
 /// Renders a divider between swimmer lanes
-fn render_lane_divider<W: Write>(
-    stdout: &mut W,
+fn render_lane_divider(
+    frame: &mut FrameBuffer,
     terminal_width: usize,
     row: u16
-) -> Result<()> {
+) {
     let divider: &str = "· · · · · · · · · · · · · · · · · · · · · · · · · · · · · · ·";
-    let divider_padding: usize = center_padding(divider.len(), terminal_width);
-    
-    execute!(
-        stdout,
-        cursor::MoveTo(divider_padding as u16, row),
-        SetForegroundColor(Color::DarkGrey),
-        Print(divider),
-        ResetColor
-    ).into_diagnostic()?;
+    let divider_padding: usize = center_padding(text_width(divider), terminal_width);
     
-    Ok(())
+    frame.set_string(divider_padding as u16, row, divider, Style::default().fg(Color::DarkGrey));
 }
@@ -0,0 +1,72 @@
+use swimming_idle::game::Game;
+use swimming_idle::modifiers::Modifiers;
+use swimming_idle::stroke::Stroke;
+use swimming_idle::swimmer::{Stat, Swimmer};
+use swimming_idle::wallet::Wallet;
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::display::widgets::{entry_style, render_entry};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Renders the upgrade menu of the selected swimmer with the price of every stat and of retraining
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `game` - The game state, for the selected swimmer, prices and the team wallet
+/// * `selected_index` - Index of the highlighted entry, one past the stats is the stroke
+/// * `retrain_to` - Stroke offered when retraining
+/// * `terminal_width` - Width of the terminal
+/// * `start_row` - Row to start drawing on
+///
+/// # Returns
+/// The current row position after rendering (for footer positioning)
+pub fn render_upgrade_menu(
+    frame: &mut FrameBuffer,
+    game: &Game,
+    selected_index: usize,
+    retrain_to: Stroke,
+    terminal_width: usize,
+    start_row: u16
+) -> u16 {
+    let swimmer: &Swimmer = &game.swimmers()[game.selected_index()];
+    let modifiers: Modifiers = game.modifiers();
+    let wallet: &Wallet = game.wallet();
+    let mut current_row: u16 = start_row + 1;
+
+    let title: String = format!("💪 Upgrade {} 💪", swimmer.name);
+    let title_padding: usize = center_padding(text_width(&title), terminal_width);
+    frame.set_string(title_padding as u16, current_row, &title, Style::default().fg(Color::Green).bold());
+    current_row += 2;
+
+    for (i, stat) in Stat::ALL.iter().enumerate() {
+        let is_selected: bool = i == selected_index;
+        let price: u64 = swimmer.upgrade_price(*stat, &modifiers);
+        let line: String = format!(
+            "{} {}: {} | {} | Next: {} lengths",
+            if is_selected { "➤" } else { " " },
+            stat.name(),
+            swimmer.display_stat(*stat),
+            stat.description(),
+            price
+        );
+        render_entry(frame, &line, is_selected, entry_style(wallet.lengths >= price), terminal_width, current_row);
+        current_row += 2;
+    }
+
+    // Retraining is listed after the stats, with the offered stroke picked using left and right
+    let is_selected: bool = selected_index == Stat::ALL.len();
+    let price: u64 = swimmer.retrain_price(&modifiers);
+    let line: String = format!(
+        "{} Stroke: {} → ◀ {} ▶ | {} | Retrain: {} lengths",
+        if is_selected { "➤" } else { " " },
+        swimmer.stroke.name(),
+        retrain_to.name(),
+        retrain_to.description(),
+        price
+    );
+    render_entry(frame, &line, is_selected, entry_style(wallet.lengths >= price), terminal_width, current_row);
+    current_row += 2;
+
+    current_row
+}
@@ -0,0 +1,48 @@
+use std::ops::Range;
+
+/// Which slice of the swimmer list is currently on screen
+#[derive(Default)]
+pub struct Viewport {
+    offset: usize,    // Index of the first swimmer shown
+    page_size: usize, // Number of lanes that fit on screen at the last render
+}
+
+impl Viewport {
+    /// Gets the number of lanes that fit on screen, used for paging
+    ///
+    /// # Returns
+    /// The page size, at least 1
+    pub fn page_size(&self) -> usize {
+        self.page_size.max(1_usize)
+    }
+
+    /// Fits the viewport to the screen and scrolls just enough to keep the selection visible
+    ///
+    /// # Arguments
+    /// * `page_size` - Number of lanes that fit on screen
+    /// * `selected_index` - Index of the currently selected swimmer
+    /// * `total` - Total number of swimmers
+    pub fn follow(&mut self, page_size: usize, selected_index: usize, total: usize) {
+        self.page_size = page_size.max(1_usize);
+
+        if selected_index < self.offset {
+            self.offset = selected_index;
+        } else if selected_index >= self.offset + self.page_size {
+            self.offset = selected_index + 1_usize - self.page_size;
+        }
+
+        // Never leave empty lanes at the bottom when the list could fill them
+        self.offset = self.offset.min(total.saturating_sub(self.page_size));
+    }
+
+    /// Gets the indices of the swimmers shown on screen
+    ///
+    /// # Arguments
+    /// * `total` - Total number of swimmers
+    ///
+    /// # Returns
+    /// The range of visible swimmer indices
+    pub fn visible_range(&self, total: usize) -> Range<usize> {
+        self.offset..(self.offset + self.page_size()).min(total)
+    }
+}
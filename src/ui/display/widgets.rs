@@ -0,0 +1,47 @@
+use crate::ui::display::buffer::{FrameBuffer, Style};
+use crate::ui::utils::{center_padding, text_width};
+
+use crossterm::style::Color;
+
+/// Gets the style of an entry that is not selected, dimmed when it cannot be bought or picked
+///
+/// # Arguments
+/// * `is_available` - Whether the entry can be bought or picked right now
+///
+/// # Returns
+/// The style to pass to `render_entry`
+pub fn entry_style(is_available: bool) -> Style {
+    if is_available {
+        Style::default().fg(Color::Grey)
+    } else {
+        Style::default().fg(Color::DarkGrey)
+    }
+}
+
+/// Renders one selectable entry centred on its row, highlighted like the selected swimmer
+///
+/// # Arguments
+/// * `frame` - The frame buffer to draw into
+/// * `line` - The entry's text, including its selection marker
+/// * `is_selected` - Whether the entry is highlighted
+/// * `style` - Style of the entry when it is not highlighted
+/// * `terminal_width` - Width of the terminal
+/// * `row` - Row to draw the entry on
+pub fn render_entry(
+    frame: &mut FrameBuffer,
+    line: &str,
+    is_selected: bool,
+    style: Style,
+    terminal_width: usize,
+    row: u16
+) {
+    let mut style: Style = style;
+    if is_selected {
+        style = Style::default().fg(Color::White).bg(Color::DarkGrey).bold();
+        let box_width: usize = terminal_width.saturating_sub(6);
+        frame.set_string(3, row, &" ".repeat(box_width), style);
+    }
+
+    let line_padding: usize = center_padding(text_width(line), terminal_width);
+    frame.set_string(line_padding as u16, row, line, style);
+}
@@ -0,0 +1,185 @@
+use swimming_idle::action::Action;
+use swimming_idle::keymap::{KeyMatch, Keymap};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::{Duration, Instant};
+
+/// How long an unfinished chord waits for its next key, like Vim's `timeoutlen`
+const CHORD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Turns key presses into actions, holding on to the start of a chord until it is finished
+#[derive(Default)]
+pub struct KeyReader {
+    pending: Vec<String>, // Names of the keys pressed so far of an unfinished chord
+    pending_since: Option<Instant>, // When the last key of the unfinished chord was pressed
+}
+
+impl KeyReader {
+    /// Reads one key press
+    ///
+    /// A key that breaks off a chord is read again on its own, so pressing
+    /// `g` then `j` still moves the selection down. A chord left unfinished
+    /// for longer than `CHORD_TIMEOUT` is forgotten.
+    ///
+    /// # Arguments
+    /// * `keymap` - The key bindings in use
+    /// * `key` - The key that was pressed
+    ///
+    /// # Returns
+    /// The action the key finishes, or `None` if it does nothing or starts a chord
+    pub fn read(&mut self, keymap: &Keymap, key: KeyEvent) -> Option<Action> {
+        self.read_at(keymap, key, Instant::now())
+    }
+
+    /// Reads one key press made at a given time
+    ///
+    /// # Arguments
+    /// * `keymap` - The key bindings in use
+    /// * `key` - The key that was pressed
+    /// * `now` - When the key was pressed
+    ///
+    /// # Returns
+    /// The action the key finishes, or `None` if it does nothing or starts a chord
+    fn read_at(&mut self, keymap: &Keymap, key: KeyEvent, now: Instant) -> Option<Action> {
+        let name: String = key_name(key)?;
+        if self
+            .pending_since
+            .is_some_and(|since: Instant| now.duration_since(since) > CHORD_TIMEOUT)
+        {
+            self.pending.clear();
+        }
+        self.pending.push(name.clone());
+        self.pending_since = Some(now);
+
+        match keymap.resolve(&self.pending) {
+            KeyMatch::Action(action) => {
+                self.pending.clear();
+                Some(action)
+            }
+            KeyMatch::Pending => None,
+            KeyMatch::Unbound if self.pending.len() > 1_usize => {
+                self.pending.clear();
+                self.read_at(keymap, key, now)
+            }
+            KeyMatch::Unbound => {
+                self.pending.clear();
+                None
+            }
+        }
+    }
+}
+
+/// Names a key press the way the config does, such as `j`, `G`, `space` or `ctrl+d`
+///
+/// # Arguments
+/// * `key` - The key that was pressed
+///
+/// # Returns
+/// The key's name, or `None` for keys that cannot be bound
+fn key_name(key: KeyEvent) -> Option<String> {
+    let base: String = match key.code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::PageUp => "pageup".to_string(),
+        KeyCode::PageDown => "pagedown".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        _ => return None,
+    };
+
+    // Shift is already in the character, `G` rather than `shift+g`
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        Some(format!("ctrl+{}", base))
+    } else if key.modifiers.contains(KeyModifiers::ALT) {
+        Some(format!("alt+{}", base))
+    } else {
+        Some(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Presses the keys named in a chord one after another, a moment apart
+    fn press(
+        reader: &mut KeyReader,
+        keymap: &Keymap,
+        chord: &str,
+        at: Instant,
+    ) -> Vec<Option<Action>> {
+        chord
+            .chars()
+            .filter(|c: &char| *c != ' ')
+            .map(|c: char| {
+                reader.read_at(
+                    keymap,
+                    KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+                    at,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chord_fires_on_its_last_key() {
+        let mut reader: KeyReader = KeyReader::default();
+
+        let actions: Vec<Option<Action>> =
+            press(&mut reader, &Keymap::default(), "g g", Instant::now());
+        assert_eq!(actions, vec![None, Some(Action::SelectFirst)]);
+    }
+
+    #[test]
+    fn broken_off_chord_reads_the_key_on_its_own() {
+        let mut reader: KeyReader = KeyReader::default();
+
+        let actions: Vec<Option<Action>> =
+            press(&mut reader, &Keymap::default(), "g j g g", Instant::now());
+        assert_eq!(
+            actions,
+            vec![
+                None,
+                Some(Action::SelectNext),
+                None,
+                Some(Action::SelectFirst)
+            ]
+        );
+    }
+
+    #[test]
+    fn unfinished_chord_is_forgotten_after_the_timeout() {
+        let keymap: Keymap = Keymap::default();
+        let mut reader: KeyReader = KeyReader::default();
+        let start: Instant = Instant::now();
+
+        assert_eq!(press(&mut reader, &keymap, "g", start), vec![None]);
+        let later: Instant = start + CHORD_TIMEOUT * 2_u32;
+        assert_eq!(press(&mut reader, &keymap, "g", later), vec![None]);
+        assert_eq!(
+            press(&mut reader, &keymap, "g", later),
+            vec![Some(Action::SelectFirst)]
+        );
+    }
+
+    #[test]
+    fn keys_that_cannot_be_bound_are_ignored() {
+        let mut reader: KeyReader = KeyReader::default();
+
+        let key: KeyEvent = KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE);
+        assert_eq!(reader.read(&Keymap::default(), key), None);
+        let ctrl_d: KeyEvent = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(
+            reader.read(&Keymap::default(), ctrl_d),
+            Some(Action::PageDown)
+        );
+    }
+}
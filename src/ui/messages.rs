@@ -1,69 +1,71 @@
-use crate::swimmer::Swimmer;
-use crate::ui::utils::center_padding;
+use swimming_idle::game::OfflineProgress;
+use swimming_idle::save::Backup;
+use crate::ui::utils::{center_padding, format_duration};
 use miette::{IntoDiagnostic, Result};
 use std::io::{stdout, Write};
-use terminal_size::{terminal_size, Height, Width};
+use std::time::{Duration, SystemTime};
+use terminal_size::{terminal_size, Width};
 
 use crossterm::{
     cursor, execute,
-    style::{
-        Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
-    },
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
     terminal::{Clear, ClearType},
 };
 
-/// Shows an upgrade message when a player attempts to upgrade a swimmer
-///
-/// # Arguments
-/// * `swimmer` - Reference to the swimmer being upgraded
-/// * `success` - Whether the upgrade was successful
+/// Shows a goodbye message when the player quits the game
 ///
 /// # Returns
 /// A Result indicating success or an error
-pub fn show_upgrade_message(swimmer: &Swimmer, success: bool) -> Result<()> {
+pub fn show_goodbye_message() -> Result<()> {
     let terminal_dimensions =
         terminal_size().ok_or_else(|| miette::miette!("Failed to get terminal size"))?;
-    let (Width(width), Height(height)) = terminal_dimensions;
+    let (Width(width), _) = terminal_dimensions;
     let terminal_width: usize = width as usize;
 
-    let message: String = if success {
-        format!(
-            "✅ {} upgraded to speed {}!",
-            swimmer.name,
-            swimmer.display_speed()
-        )
-    } else {
-        format!(
-            "❌ Not enough lengths! Need {} more for upgrade.",
-            swimmer.upgrade_cost as i32 - swimmer.lengths
-        )
-    };
+    execute!(stdout(), Clear(ClearType::All), cursor::MoveTo(0, 0)).into_diagnostic()?;
+
+    // Draw a decorative top border
+    let border: String = "═".repeat(terminal_width);
+    execute!(
+        stdout(),
+        cursor::MoveTo(0, 2),
+        SetForegroundColor(Color::Cyan),
+        Print(&border),
+        ResetColor
+    )
+    .into_diagnostic()?;
 
-    let padding: usize = center_padding(message.len() + 6, terminal_width);
-    let message_row: u16 = height.saturating_sub(3);
+    // Goodbye message
+    let title: &str = "🏊 Thanks for playing Swimming Idle Game! 🏊";
+    let subtitle: &str = "Your swimmers will miss you...";
+    let exit_msg: &str = "Press any key to exit...";
 
-    // Create a box for the message
     execute!(
         stdout(),
-        cursor::SavePosition,
-        cursor::MoveTo(padding as u16, message_row),
-        if success {
-            SetBackgroundColor(Color::DarkGreen)
-        } else {
-            SetBackgroundColor(Color::DarkRed)
-        },
-        Print("  "),
-        if success {
-            SetForegroundColor(Color::Green)
-        } else {
-            SetForegroundColor(Color::Red)
-        },
+        cursor::MoveTo(center_padding(title.len(), terminal_width) as u16, 4),
+        SetForegroundColor(Color::Cyan),
         SetAttribute(Attribute::Bold),
-        Print(&message),
-        Print("  "),
+        Print(title),
         SetAttribute(Attribute::Reset),
         ResetColor,
-        cursor::RestorePosition
+        cursor::MoveTo(center_padding(subtitle.len(), terminal_width) as u16, 6),
+        SetForegroundColor(Color::White),
+        Print(subtitle),
+        ResetColor,
+        cursor::MoveTo(center_padding(exit_msg.len(), terminal_width) as u16, 8),
+        SetForegroundColor(Color::Grey),
+        Print(exit_msg),
+        ResetColor
+    )
+    .into_diagnostic()?;
+
+    // Draw a decorative bottom border
+    execute!(
+        stdout(),
+        cursor::MoveTo(0, 10),
+        SetForegroundColor(Color::Cyan),
+        Print(&border),
+        ResetColor
     )
     .into_diagnostic()?;
 
@@ -71,63 +73,127 @@ pub fn show_upgrade_message(swimmer: &Swimmer, success: bool) -> Result<()> {
     Ok(())
 }
 
-/// Shows a message when a player attempts to add a new swimmer
+/// Shows a summary of the lengths earned while the game was closed
 ///
 /// # Arguments
-/// * `swimmer` - Reference to the new swimmer (or first swimmer if failed)
-/// * `success` - Whether adding the swimmer was successful
-/// * `cost` - Optional cost for a new swimmer, used for failure message
+/// * `offline_progress` - The progress credited since the last session
 ///
 /// # Returns
 /// A Result indicating success or an error
-pub fn show_new_swimmer_message(
-    swimmer: &Swimmer,
-    success: bool,
-    cost: Option<usize>,
-) -> Result<()> {
+pub fn show_offline_summary(offline_progress: &OfflineProgress) -> Result<()> {
     let terminal_dimensions =
         terminal_size().ok_or_else(|| miette::miette!("Failed to get terminal size"))?;
-    let (Width(width), Height(height)) = terminal_dimensions;
+    let (Width(width), _) = terminal_dimensions;
     let terminal_width: usize = width as usize;
 
-    let message: String = if success {
-        format!(
-            "✅ New swimmer {} joined with speed {}!",
-            swimmer.name,
-            swimmer.display_speed()
+    execute!(stdout(), Clear(ClearType::All), cursor::MoveTo(0, 0)).into_diagnostic()?;
+
+    // Draw a decorative top border
+    let border: String = "═".repeat(terminal_width);
+    execute!(
+        stdout(),
+        cursor::MoveTo(0, 2),
+        SetForegroundColor(Color::Cyan),
+        Print(&border),
+        ResetColor
+    )
+    .into_diagnostic()?;
+
+    let title: &str = "🏊 While you were away... 🏊";
+    let subtitle: String = format!(
+        "Your swimmers kept training for {}",
+        format_duration(offline_progress.elapsed)
+    );
+
+    execute!(
+        stdout(),
+        cursor::MoveTo(center_padding(title.len(), terminal_width) as u16, 4),
+        SetForegroundColor(Color::Cyan),
+        SetAttribute(Attribute::Bold),
+        Print(title),
+        SetAttribute(Attribute::Reset),
+        ResetColor,
+        cursor::MoveTo(center_padding(subtitle.len(), terminal_width) as u16, 6),
+        SetForegroundColor(Color::White),
+        Print(&subtitle),
+        ResetColor
+    )
+    .into_diagnostic()?;
+
+    // One line per swimmer with the lengths they completed
+    let mut row: u16 = 8;
+    for (name, lengths) in &offline_progress.lengths_by_swimmer {
+        let line: String = format!("{}: +{} lengths", name, lengths);
+        execute!(
+            stdout(),
+            cursor::MoveTo(center_padding(line.len(), terminal_width) as u16, row),
+            SetForegroundColor(Color::Green),
+            Print(&line),
+            ResetColor
+        )
+        .into_diagnostic()?;
+        row += 1;
+    }
+
+    // Weekly league meets the club raced without its manager
+    if offline_progress.league_weeks > 0 {
+        let line: String = format!("League meets raced: {}", offline_progress.league_weeks);
+        execute!(
+            stdout(),
+            cursor::MoveTo(center_padding(line.len(), terminal_width) as u16, row),
+            SetForegroundColor(Color::Yellow),
+            Print(&line),
+            ResetColor
         )
-    } else {
-        format!(
-            "❌ Not enough lengths! Need {} more for a new swimmer.",
-            cost.unwrap_or(0) as i32 - swimmer.lengths
+        .into_diagnostic()?;
+        row += 1;
+    }
+
+    // Relays swum in turn, with the bonus lengths they earned
+    if offline_progress.relays > 0 {
+        let line: String = format!(
+            "Relays completed: {} (+{} bonus lengths)",
+            offline_progress.relays, offline_progress.relay_bonus_lengths
+        );
+        execute!(
+            stdout(),
+            cursor::MoveTo(center_padding(line.len(), terminal_width) as u16, row),
+            SetForegroundColor(Color::Magenta),
+            Print(&line),
+            ResetColor
         )
-    };
+        .into_diagnostic()?;
+        row += 1;
+    }
 
-    let padding: usize = center_padding(message.len() + 6, terminal_width);
-    let message_row: u16 = height.saturating_sub(3);
+    let total_lengths: u64 = offline_progress
+        .lengths_by_swimmer
+        .iter()
+        .map(|(_, lengths)| lengths)
+        .sum::<u64>()
+        + offline_progress.relay_bonus_lengths;
+    let total: String = format!("Total: +{} lengths", total_lengths);
+    let continue_msg: &str = "Press any key to continue...";
 
-    // Create a box for the message
     execute!(
         stdout(),
-        cursor::SavePosition,
-        cursor::MoveTo(padding as u16, message_row),
-        if success {
-            SetBackgroundColor(Color::DarkGreen)
-        } else {
-            SetBackgroundColor(Color::DarkRed)
-        },
-        Print("  "),
-        if success {
-            SetForegroundColor(Color::Green)
-        } else {
-            SetForegroundColor(Color::Red)
-        },
+        cursor::MoveTo(center_padding(total.len(), terminal_width) as u16, row + 1),
+        SetForegroundColor(Color::Green),
         SetAttribute(Attribute::Bold),
-        Print(&message),
-        Print("  "),
+        Print(&total),
         SetAttribute(Attribute::Reset),
         ResetColor,
-        cursor::RestorePosition
+        cursor::MoveTo(
+            center_padding(continue_msg.len(), terminal_width) as u16,
+            row + 3
+        ),
+        SetForegroundColor(Color::Grey),
+        Print(continue_msg),
+        ResetColor,
+        cursor::MoveTo(0, row + 5),
+        SetForegroundColor(Color::Cyan),
+        Print(&border),
+        ResetColor
     )
     .into_diagnostic()?;
 
@@ -135,11 +201,15 @@ pub fn show_new_swimmer_message(
     Ok(())
 }
 
-/// Shows a goodbye message when the player quits the game
+/// Shows the menu for restoring a backup when the save file is corrupt
+///
+/// # Arguments
+/// * `backups` - The backups available to restore, newest first
+/// * `selected_index` - Index of the currently highlighted backup
 ///
 /// # Returns
 /// A Result indicating success or an error
-pub fn show_goodbye_message() -> Result<()> {
+pub fn show_restore_menu(backups: &[Backup], selected_index: usize) -> Result<()> {
     let terminal_dimensions =
         terminal_size().ok_or_else(|| miette::miette!("Failed to get terminal size"))?;
     let (Width(width), _) = terminal_dimensions;
@@ -152,21 +222,19 @@ pub fn show_goodbye_message() -> Result<()> {
     execute!(
         stdout(),
         cursor::MoveTo(0, 2),
-        SetForegroundColor(Color::Cyan),
+        SetForegroundColor(Color::Red),
         Print(&border),
         ResetColor
     )
     .into_diagnostic()?;
 
-    // Goodbye message
-    let title: &str = "🏊 Thanks for playing Swimming Idle Game! 🏊";
-    let subtitle: &str = "Your swimmers will miss you...";
-    let exit_msg: &str = "Press any key to exit...";
+    let title: &str = "⚠ Your save file could not be loaded ⚠";
+    let subtitle: &str = "Choose a backup to restore:";
 
     execute!(
         stdout(),
         cursor::MoveTo(center_padding(title.len(), terminal_width) as u16, 4),
-        SetForegroundColor(Color::Cyan),
+        SetForegroundColor(Color::Red),
         SetAttribute(Attribute::Bold),
         Print(title),
         SetAttribute(Attribute::Reset),
@@ -174,19 +242,59 @@ pub fn show_goodbye_message() -> Result<()> {
         cursor::MoveTo(center_padding(subtitle.len(), terminal_width) as u16, 6),
         SetForegroundColor(Color::White),
         Print(subtitle),
-        ResetColor,
-        cursor::MoveTo(center_padding(exit_msg.len(), terminal_width) as u16, 8),
-        SetForegroundColor(Color::Grey),
-        Print(exit_msg),
         ResetColor
     )
     .into_diagnostic()?;
 
-    // Draw a decorative bottom border
+    // One line per backup, with the highlighted one marked
+    let mut row: u16 = 8;
+    for (i, backup) in backups.iter().enumerate() {
+        let is_selected: bool = i == selected_index;
+        let age: String = backup
+            .modified
+            .and_then(|modified: SystemTime| modified.elapsed().ok())
+            .map(|elapsed: Duration| format!("saved {} ago", format_duration(elapsed)))
+            .unwrap_or_else(|| "unknown age".to_string());
+        let line: String = format!(
+            "{} Backup {} ({})",
+            if is_selected { "➤" } else { " " },
+            i + 1_usize,
+            age
+        );
+
+        execute!(
+            stdout(),
+            cursor::MoveTo(center_padding(line.len(), terminal_width) as u16, row),
+            SetForegroundColor(if is_selected {
+                Color::White
+            } else {
+                Color::Grey
+            }),
+            SetAttribute(if is_selected {
+                Attribute::Bold
+            } else {
+                Attribute::Reset
+            }),
+            Print(&line),
+            SetAttribute(Attribute::Reset),
+            ResetColor
+        )
+        .into_diagnostic()?;
+        row += 1;
+    }
+
+    let controls: &str = "[↑/↓] Select | [Enter] Restore | [q] Quit";
     execute!(
         stdout(),
-        cursor::MoveTo(0, 10),
-        SetForegroundColor(Color::Cyan),
+        cursor::MoveTo(
+            center_padding(controls.len(), terminal_width) as u16,
+            row + 1
+        ),
+        SetForegroundColor(Color::Yellow),
+        Print(controls),
+        ResetColor,
+        cursor::MoveTo(0, row + 3),
+        SetForegroundColor(Color::Red),
         Print(&border),
         ResetColor
     )
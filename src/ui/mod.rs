@@ -1,9 +1,10 @@
+mod app;
 mod messages;
 mod display;
+mod input;
+mod terminal;
 mod utils;
 
-pub use crate::ui::messages::{
-    show_goodbye_message, show_new_swimmer_message, show_upgrade_message,
-};
+pub use crate::ui::app::{choose_backup, run};
 
-pub use crate::ui::display::display_ui;
+pub use crate::ui::terminal::install_panic_hook;
@@ -0,0 +1,137 @@
+use miette::{IntoDiagnostic, Result};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+use signal_hook::SigId;
+use std::io::stdout;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+/// Owns the terminal while the game is drawing to it
+///
+/// Creating a session switches to the alternate screen in raw mode with a
+/// hidden cursor, and dropping it puts everything back, including when the
+/// session is dropped by a `?` error or unwinding from a panic.
+pub struct TerminalSession {
+    interrupted: Arc<AtomicBool>, // Set when SIGINT/SIGTERM/SIGHUP or Ctrl+C is received
+    signal_ids: Vec<SigId>,       // Signal handlers to unregister on drop
+}
+
+impl TerminalSession {
+    /// Takes over the terminal and starts listening for termination signals
+    ///
+    /// # Returns
+    /// A Result containing the new TerminalSession or an error
+    pub fn start() -> Result<Self> {
+        // Undo whatever part of the setup succeeded if a later step fails, handlers included
+        let mut signal_ids = scopeguard::guard(Vec::<SigId>::new(), |signal_ids: Vec<SigId>| {
+            for signal_id in signal_ids {
+                signal_hook::low_level::unregister(signal_id);
+            }
+            restore_terminal();
+        });
+
+        enable_raw_mode().into_diagnostic()?;
+        execute!(stdout(), EnterAlternateScreen, cursor::Hide).into_diagnostic()?;
+
+        let interrupted: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+        for signal in [SIGINT, SIGTERM, SIGHUP] {
+            // A second signal while the first is still being handled kills the process.
+            // Both handlers go away with the session, so a signal after it has ended does not
+            signal_ids.push(
+                signal_hook::flag::register_conditional_shutdown(signal, 1, Arc::clone(&interrupted))
+                    .into_diagnostic()?,
+            );
+            signal_ids.push(
+                signal_hook::flag::register(signal, Arc::clone(&interrupted)).into_diagnostic()?,
+            );
+        }
+
+        Ok(Self {
+            interrupted,
+            signal_ids: scopeguard::ScopeGuard::into_inner(signal_ids),
+        })
+    }
+
+    /// Checks whether the player asked to stop via a signal or Ctrl+C
+    ///
+    /// # Returns
+    /// `true` if the session was interrupted
+    pub fn interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::Relaxed)
+    }
+
+    /// Polls for a key press, treating Ctrl+C as an interrupt
+    ///
+    /// # Arguments
+    /// * `timeout` - How long to wait for an event
+    ///
+    /// # Returns
+    /// A Result containing the key pressed, or `None` if there was none
+    pub fn poll_key(&self, timeout: Duration) -> Result<Option<KeyEvent>> {
+        if !event::poll(timeout).into_diagnostic()? {
+            return Ok(None);
+        }
+
+        match event::read().into_diagnostic()? {
+            Event::Key(key)
+                if key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                // Raw mode swallows SIGINT, so Ctrl+C arrives as a key instead
+                self.interrupted.store(true, Ordering::Relaxed);
+                Ok(None)
+            }
+            Event::Key(key) => Ok(Some(key)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Blocks until a key is pressed or the session is interrupted
+    ///
+    /// # Returns
+    /// A Result containing the key pressed, or `None` if the session was interrupted
+    pub fn wait_for_key(&self) -> Result<Option<KeyEvent>> {
+        while !self.interrupted() {
+            if let Some(key) = self.poll_key(Duration::from_millis(100_u64))? {
+                return Ok(Some(key));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        for signal_id in self.signal_ids.drain(..) {
+            signal_hook::low_level::unregister(signal_id);
+        }
+        restore_terminal();
+    }
+}
+
+/// Puts the terminal back into its normal state
+///
+/// Errors are ignored because this runs on cleanup paths where there is
+/// nothing better to do than try every step.
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(stdout(), cursor::Show, LeaveAlternateScreen);
+}
+
+/// Installs a panic hook that restores the terminal before printing the panic report
+pub fn install_panic_hook() {
+    miette::set_panic_hook();
+    let report_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        report_hook(info);
+    }));
+}
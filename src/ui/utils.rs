@@ -1,3 +1,6 @@
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
+
 /// Calculates the left padding needed to center text in the terminal
 ///
 /// # Arguments
@@ -13,3 +16,69 @@ pub fn center_padding(text_width: usize, terminal_width: usize) -> usize {
         (terminal_width - text_width) / 2_usize
     }
 }
+
+/// Gets the number of terminal columns a string takes up
+///
+/// Unlike `str::len` this counts emoji as two columns and multi-byte
+/// characters such as box drawing lines as one.
+///
+/// # Arguments
+/// * `text` - The text to measure
+///
+/// # Returns
+/// The display width of the text
+pub fn text_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Formats a duration as a short human readable string (e.g. "2h 5m")
+///
+/// # Arguments
+/// * `duration` - The duration to format
+///
+/// # Returns
+/// The formatted duration, using the two largest non-zero units
+pub fn format_duration(duration: Duration) -> String {
+    let total_secs: u64 = duration.as_secs();
+    let days: u64 = total_secs / 86_400_u64;
+    let hours: u64 = (total_secs % 86_400_u64) / 3_600_u64;
+    let minutes: u64 = (total_secs % 3_600_u64) / 60_u64;
+    let seconds: u64 = total_secs % 60_u64;
+
+    if days > 0_u64 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0_u64 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0_u64 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Draws a series of values as a one-line sparkline of block characters
+///
+/// # Arguments
+/// * `values` - The values to draw, oldest first
+///
+/// # Returns
+/// One block per value, scaled between the smallest and largest value
+pub fn sparkline<'a>(values: impl Iterator<Item = &'a f64> + Clone) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min: f64 = values.clone().copied().fold(f64::INFINITY, f64::min);
+    let max: f64 = values.clone().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range: f64 = max - min;
+
+    values
+        .map(|value: &f64| {
+            // A flat line sits in the middle rather than at the bottom
+            let level: usize = if range <= f64::EPSILON {
+                BLOCKS.len() / 2_usize
+            } else {
+                ((value - min) / range * (BLOCKS.len() - 1_usize) as f64).round() as usize
+            };
+            BLOCKS[level.min(BLOCKS.len() - 1_usize)]
+        })
+        .collect()
+}
@@ -1,25 +1,25 @@
-use rand::rng;
+use crate::rng::GameRng;
 use miette::{IntoDiagnostic, Result};
 use rand::prelude::*;
 use serde::Deserialize;
-use std::fs;
-use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub struct NameData {
     pub first_names: Vec<String>,
     pub last_names: Vec<String>,
     pub nicknames: Vec<String>,
 }
 
-/// Loads swimmer name data from JSON file
+/// Swimmer names, built into the game so it runs from any directory
+const NAME_DATA: &str = include_str!("../data/swimmer_names.json");
+
+/// Loads swimmer name data from the JSON built into the game
 ///
 /// # Returns
 /// A Result containing the NameData or an error
 pub fn load_name_data() -> Result<NameData> {
-    let path: &Path = Path::new("src/data/swimmer_names.json");
-    let data: String = fs::read_to_string(path).into_diagnostic()?;
-    let name_data: NameData = serde_json::from_str::<NameData>(&data).into_diagnostic()?;
+    let name_data: NameData = serde_json::from_str::<NameData>(NAME_DATA).into_diagnostic()?;
     Ok(name_data)
 }
 
@@ -27,11 +27,11 @@ pub fn load_name_data() -> Result<NameData> {
 ///
 /// # Arguments
 /// * `name_data` - Reference to name data structure
+/// * `rng` - The game's random number generator
 ///
 /// # Returns
 /// A String containing the generated name
-pub fn generate_random_name(name_data: &NameData) -> String {
-    let mut rng: ThreadRng = rng();
+pub fn generate_random_name(name_data: &NameData, rng: &mut GameRng) -> String {
     let use_nickname: bool = rng.random_bool(0.3_f64); // 30% chance to use a nickname
 
     if use_nickname {
@@ -52,3 +52,14 @@ pub fn generate_random_name(name_data: &NameData) -> String {
         format!("{} {}", firstname, lastname)
     }
 }
+
+/// Gets the current time as seconds since the Unix epoch
+///
+/// # Returns
+/// The number of seconds elapsed since 1970-01-01 00:00:00 UTC
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0_u64)
+}
@@ -0,0 +1,249 @@
+use crate::config::ExchangeConfig;
+use serde::{Deserialize, Serialize};
+
+/// The team's shared treasury that every earned length flows into
+#[derive(Default, Serialize, Deserialize)]
+pub struct Wallet {
+    pub lengths: u64, // Lengths available to spend on upgrades and hires
+    #[serde(default)]
+    pub points: u64, // Points available to spend in the shop
+    #[serde(default)]
+    pub points_converted: u64, // Points ever bought with lengths, makes the next ones pricier
+    #[serde(default)]
+    pub prize_money: u64, // Prize money won at meets
+}
+
+impl Wallet {
+    /// Adds earned lengths to the treasury
+    ///
+    /// # Arguments
+    /// * `amount` - Number of lengths earned
+    pub fn deposit_lengths(&mut self, amount: u64) {
+        self.lengths += amount;
+    }
+
+    /// Adds prize money won at a meet to the treasury
+    ///
+    /// # Arguments
+    /// * `amount` - Prize money won
+    pub fn deposit_prize_money(&mut self, amount: u64) {
+        self.prize_money += amount;
+    }
+
+    /// Spends lengths from the treasury if there are enough
+    ///
+    /// # Arguments
+    /// * `cost` - Number of lengths to spend
+    ///
+    /// # Returns
+    /// `true` if the lengths were spent, `false` if the treasury is short
+    pub fn spend_lengths(&mut self, cost: u64) -> bool {
+        if self.lengths < cost {
+            return false;
+        }
+        self.lengths -= cost;
+        true
+    }
+
+    /// Gets how many more lengths are needed to afford a purchase
+    ///
+    /// # Arguments
+    /// * `cost` - Number of lengths the purchase costs
+    ///
+    /// # Returns
+    /// The shortfall, or 0 if the purchase is affordable
+    pub fn lengths_needed(&self, cost: u64) -> u64 {
+        cost.saturating_sub(self.lengths)
+    }
+
+    /// Spends points from the treasury if there are enough
+    ///
+    /// # Arguments
+    /// * `cost` - Number of points to spend
+    ///
+    /// # Returns
+    /// `true` if the points were spent, `false` if the treasury is short
+    pub fn spend_points(&mut self, cost: u64) -> bool {
+        if self.points < cost {
+            return false;
+        }
+        self.points -= cost;
+        true
+    }
+
+    /// Gets how many more points are needed to afford a purchase
+    ///
+    /// # Arguments
+    /// * `cost` - Number of points the purchase costs
+    ///
+    /// # Returns
+    /// The shortfall, or 0 if the purchase is affordable
+    pub fn points_needed(&self, cost: u64) -> u64 {
+        cost.saturating_sub(self.points)
+    }
+
+    /// Spends prize money from the treasury if there is enough
+    ///
+    /// # Arguments
+    /// * `cost` - Prize money to spend
+    ///
+    /// # Returns
+    /// `true` if the prize money was spent, `false` if the treasury is short
+    pub fn spend_prize_money(&mut self, cost: u64) -> bool {
+        if self.prize_money < cost {
+            return false;
+        }
+        self.prize_money -= cost;
+        true
+    }
+
+    /// Gets how much more prize money is needed to afford a purchase
+    ///
+    /// # Arguments
+    /// * `cost` - Prize money the purchase costs
+    ///
+    /// # Returns
+    /// The shortfall, or 0 if the purchase is affordable
+    pub fn prize_money_needed(&self, cost: u64) -> u64 {
+        cost.saturating_sub(self.prize_money)
+    }
+
+    /// Gets the price of the next point, which rises with every point converted
+    ///
+    /// # Arguments
+    /// * `exchange` - The exchange rate settings
+    ///
+    /// # Returns
+    /// The price in lengths
+    pub fn point_price(&self, exchange: &ExchangeConfig) -> u64 {
+        total_price(exchange, self.points_converted + 1_u64)
+            - total_price(exchange, self.points_converted)
+    }
+
+    /// Converts as many lengths into points as the treasury can afford
+    ///
+    /// # Arguments
+    /// * `exchange` - The exchange rate settings
+    ///
+    /// # Returns
+    /// The number of points gained
+    pub fn convert_to_points(&mut self, exchange: &ExchangeConfig) -> u64 {
+        // Solve `per_point * (total + rate * total * (total - 1) / 2) = budget` for the total points
+        let per_point: f64 = exchange.lengths_per_point.max(1_f64);
+        let rate: f64 = exchange.diminishing_rate.max(0_f64);
+        let budget: u64 = self.lengths + total_price(exchange, self.points_converted);
+        let b: f64 = 1_f64 - rate / 2_f64;
+        let c: f64 = budget as f64 / per_point;
+        let estimate: f64 = if rate > 0_f64 {
+            ((b * b + 2_f64 * rate * c).sqrt() - b) / rate
+        } else {
+            c
+        };
+
+        // Rounding can leave the estimate a point out either way
+        let mut total: u64 = (estimate.max(0_f64) as u64).max(self.points_converted);
+        while total > self.points_converted && total_price(exchange, total) > budget {
+            total -= 1_u64;
+        }
+        while total_price(exchange, total + 1_u64) <= budget {
+            total += 1_u64;
+        }
+
+        let converted: u64 = total - self.points_converted;
+        self.lengths = budget - total_price(exchange, total);
+        self.points += converted;
+        self.points_converted = total;
+        converted
+    }
+
+    /// Empties the season's currencies for a new season, prize money is kept for the facilities
+    pub fn start_season(&mut self) {
+        *self = Self {
+            prize_money: self.prize_money,
+            ..Self::default()
+        };
+    }
+}
+
+/// Gets the price of every point converted so far together
+///
+/// Each point costs a fixed step more than the one before, so the sum is an
+/// arithmetic series. It is rounded up as a whole, which keeps the price of
+/// a single point at least one length and the same whether points are
+/// converted one at a time or all at once.
+///
+/// # Arguments
+/// * `exchange` - The exchange rate settings
+/// * `count` - Number of points converted
+///
+/// # Returns
+/// The price in lengths
+fn total_price(exchange: &ExchangeConfig, count: u64) -> u64 {
+    let per_point: f64 = exchange.lengths_per_point.max(1_f64);
+    let rate: f64 = exchange.diminishing_rate.max(0_f64);
+    let count: f64 = count as f64;
+    (per_point * (count + rate * count * (count - 1_f64) / 2_f64)).ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_season_keeps_prize_money() {
+        let mut wallet: Wallet = Wallet {
+            lengths: 120_u64,
+            points: 4_u64,
+            points_converted: 9_u64,
+            prize_money: 300_u64,
+        };
+
+        wallet.start_season();
+
+        assert_eq!(wallet.lengths, 0_u64);
+        assert_eq!(wallet.points, 0_u64);
+        assert_eq!(wallet.points_converted, 0_u64);
+        assert_eq!(wallet.prize_money, 300_u64);
+    }
+
+    #[test]
+    fn converting_buys_the_points_one_by_one_would() {
+        let exchange: ExchangeConfig = ExchangeConfig::default();
+        let mut one_by_one: Wallet = Wallet {
+            lengths: 1_234_u64,
+            points_converted: 7_u64,
+            ..Wallet::default()
+        };
+        let mut at_once: Wallet = Wallet {
+            lengths: 1_234_u64,
+            points_converted: 7_u64,
+            ..Wallet::default()
+        };
+
+        while one_by_one.spend_lengths(one_by_one.point_price(&exchange)) {
+            one_by_one.points += 1_u64;
+            one_by_one.points_converted += 1_u64;
+        }
+        let converted: u64 = at_once.convert_to_points(&exchange);
+
+        assert_eq!(converted, one_by_one.points);
+        assert_eq!(at_once.lengths, one_by_one.lengths);
+        assert_eq!(at_once.points_converted, one_by_one.points_converted);
+        assert!(at_once.lengths < at_once.point_price(&exchange));
+    }
+
+    #[test]
+    fn converting_a_huge_balance_at_a_flat_rate_is_instant() {
+        let exchange: ExchangeConfig = ExchangeConfig {
+            lengths_per_point: 10_f64,
+            diminishing_rate: 0_f64,
+        };
+        let mut wallet: Wallet = Wallet {
+            lengths: 10_000_000_000_005_u64,
+            ..Wallet::default()
+        };
+
+        assert_eq!(wallet.convert_to_points(&exchange), 1_000_000_000_000_u64);
+        assert_eq!(wallet.lengths, 5_u64);
+    }
+}